@@ -0,0 +1,51 @@
+use std::fmt;
+
+use typed_key::Key;
+
+/// A key that carries its own default value, which is used when the key is absent
+///
+/// Non-capturing closures can be used for the default, so a key can be declared as a
+/// constant: `const RETRIES: DefaultKey<u32> = DefaultKey::new(typed_key!("retries"), || 3);`
+pub struct DefaultKey<T> {
+    key: Key<T>,
+    default: fn() -> T,
+}
+
+impl<T> DefaultKey<T> {
+    pub const fn new(key: Key<T>, default: fn() -> T) -> Self {
+        DefaultKey { key, default }
+    }
+
+    pub fn key(&self) -> Key<T> {
+        self.key
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.key.name()
+    }
+
+    /// Creates the default value for this key
+    pub fn default_value(&self) -> T {
+        (self.default)()
+    }
+}
+
+impl<T> Copy for DefaultKey<T> {}
+
+impl<T> Clone for DefaultKey<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> fmt::Debug for DefaultKey<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "DefaultKey({:?})", self.key.name())
+    }
+}
+
+impl<T> From<DefaultKey<T>> for Key<T> {
+    fn from(key: DefaultKey<T>) -> Self {
+        key.key
+    }
+}
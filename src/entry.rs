@@ -0,0 +1,107 @@
+use std::marker::PhantomData;
+
+use crate::map;
+use crate::{FreeformData, FreeformErr, FreeformMut, SerdeScheme, Sord};
+
+/// A view into a single entry of a Freeform, returned by [`Freeform::entry`](crate::Freeform::entry)
+///
+/// If the entry is occupied, the stored value has already been checked to deserialize as a `T`.
+pub enum Entry<'a, T, S: SerdeScheme> {
+    Occupied(OccupiedEntry<'a, T, S>),
+    Vacant(VacantEntry<'a, T, S>),
+}
+
+pub struct OccupiedEntry<'a, T, S: SerdeScheme> {
+    pub(crate) entry: map::OccupiedEntry<'a, String, Sord<S>>,
+    pub(crate) _marker: PhantomData<T>,
+}
+
+pub struct VacantEntry<'a, T, S: SerdeScheme> {
+    pub(crate) entry: map::VacantEntry<'a, String, Sord<S>>,
+    pub(crate) _marker: PhantomData<T>,
+}
+
+impl<'a, T: FreeformData, S: SerdeScheme> Entry<'a, T, S> {
+    pub fn key(&self) -> &str {
+        match self {
+            Entry::Occupied(entry) => entry.key(),
+            Entry::Vacant(entry) => entry.key(),
+        }
+    }
+
+    pub fn or_insert(self, default: T) -> Result<FreeformMut<'a, T>, FreeformErr<S>> {
+        self.or_insert_with(|| default)
+    }
+
+    pub fn or_insert_with<F: FnOnce() -> T>(
+        self,
+        default: F,
+    ) -> Result<FreeformMut<'a, T>, FreeformErr<S>> {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(default()),
+        }
+    }
+
+    pub fn or_default(self) -> Result<FreeformMut<'a, T>, FreeformErr<S>>
+    where
+        T: Default,
+    {
+        self.or_insert_with(T::default)
+    }
+
+    /// Modifies the value if the entry is occupied
+    pub fn and_modify<F: FnOnce(&mut T)>(self, f: F) -> Result<Self, FreeformErr<S>> {
+        match self {
+            Entry::Occupied(mut entry) => {
+                f(&mut *entry.get_mut()?);
+                Ok(Entry::Occupied(entry))
+            }
+            Entry::Vacant(entry) => Ok(Entry::Vacant(entry)),
+        }
+    }
+}
+
+impl<'a, T: FreeformData, S: SerdeScheme> OccupiedEntry<'a, T, S> {
+    pub fn key(&self) -> &str {
+        self.entry.key()
+    }
+
+    pub fn get(&self) -> Result<&T, FreeformErr<S>> {
+        Ok(self.entry.get().de::<T>()?)
+    }
+
+    pub fn get_mut(&mut self) -> Result<FreeformMut<'_, T>, FreeformErr<S>> {
+        Ok(FreeformMut {
+            de: self.entry.get_mut().de_mut::<T>()?,
+        })
+    }
+
+    pub fn into_mut(self) -> Result<FreeformMut<'a, T>, FreeformErr<S>> {
+        Ok(FreeformMut {
+            de: self.entry.into_mut().de_mut::<T>()?,
+        })
+    }
+
+    /// Replaces the value, returning the Sord that was stored before
+    pub fn insert(&mut self, value: T) -> Sord<S> {
+        self.entry.insert(Sord::from_de(value))
+    }
+
+    /// Removes the entry, returning the deserialized value
+    pub fn remove(self) -> Result<T, FreeformErr<S>> {
+        Ok(map::remove_entry(self.entry).into_de::<T>()?)
+    }
+}
+
+impl<'a, T: FreeformData, S: SerdeScheme> VacantEntry<'a, T, S> {
+    pub fn key(&self) -> &str {
+        self.entry.key()
+    }
+
+    pub fn insert(self, value: T) -> Result<FreeformMut<'a, T>, FreeformErr<S>> {
+        Ok(FreeformMut {
+            de: self.entry.insert(Sord::from_de(value)).de_mut::<T>()?,
+        })
+    }
+}
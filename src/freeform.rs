@@ -1,34 +1,38 @@
-use std::collections::HashMap;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::{Hash, Hasher};
+use std::iter::FusedIterator;
+use std::marker::PhantomData;
+use std::ops::{Deref, DerefMut};
 
 use bevy_reflect::Reflect;
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
-use typed_key::Key;
+use typed_key::{typed_key, Key};
 
+use crate::map::{self, Map};
+use crate::merge::Combiner;
 #[cfg(feature = "json")]
 use crate::Json;
-use crate::SerdeScheme;
-use crate::{Sord, SordError};
+use crate::{BorrowingSerdeScheme, SerdeScheme};
+use crate::{
+    ConvertError, DefaultKey, Entry, FreeformPatch, KeyPath, MergePolicy, MergeStrategy,
+    OccupiedEntry, PatchOp, Sord, SordError, TypedSord, VacantEntry,
+};
 
 #[cfg(feature = "json")]
-#[derive(Clone, Debug, Default, Serialize, Deserialize, Reflect)]
-#[serde(
-    try_from = "HashMap<String, S::Value>",
-    into = "HashMap<String, S::Value>"
-)]
+#[derive(Clone, Debug, Default, Deserialize, Reflect)]
+#[serde(try_from = "Map<String, S::Value>")]
 pub struct Freeform<S: SerdeScheme = Json>(
-    #[serde(bound(serialize = "", deserialize = ""))] HashMap<String, Sord<S>>,
+    #[serde(bound(deserialize = ""))] pub(crate) Map<String, Sord<S>>,
 );
 
 #[cfg(not(feature = "json"))]
-#[derive(Clone, Debug, Default, Serialize, Deserialize, Reflect)]
-#[serde(
-    try_from = "HashMap<String, S::Value>",
-    into = "HashMap<String, S::Value>"
-)]
+#[derive(Clone, Debug, Default, Deserialize, Reflect)]
+#[serde(try_from = "Map<String, S::Value>")]
 pub struct Freeform<S: SerdeScheme>(
-    #[serde(bound(serialize = "", deserialize = ""))] HashMap<String, Sord<S>>,
+    #[serde(bound(deserialize = ""))] pub(crate) Map<String, Sord<S>>,
 );
 
 /// Trait for data that is generally compatible with being stored in a Freeform
@@ -36,6 +40,28 @@ pub trait FreeformData: Serialize + DeserializeOwned + 'static + Send + Sync {}
 
 impl<T: Serialize + DeserializeOwned + 'static + Send + Sync> FreeformData for T {}
 
+/// Mutable access to a value stored in a Freeform, returned by [`Freeform::get_mut`]
+///
+/// The cached serialized form of the entry is invalidated when this is created, and is
+/// serialized again from the modified value the next time it is needed.
+#[derive(Debug)]
+pub struct FreeformMut<'a, T> {
+    pub(crate) de: &'a mut T,
+}
+
+impl<'a, T> Deref for FreeformMut<'a, T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        self.de
+    }
+}
+
+impl<'a, T> DerefMut for FreeformMut<'a, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        self.de
+    }
+}
+
 #[derive(Clone, Debug, Error)]
 pub enum FreeformErr<S: SerdeScheme> {
     #[error("error from serde_json in metadata: {0}")]
@@ -44,6 +70,18 @@ pub enum FreeformErr<S: SerdeScheme> {
     RequiredKeyNotFound(String),
     #[error("The key type doesn't match what was stored")]
     KeyTypeDoesNotMatch,
+    #[error("conflicting values for key [{0}]")]
+    KeyConflict(String),
+    #[error("could not serialize key [{key}]: {error}")]
+    KeySerdeError { key: String, error: S::Error },
+}
+
+/// Error from converting a Freeform to another scheme, along with the key that failed
+#[derive(Clone, Debug, Error)]
+#[error("could not convert key [{key}]: {error}")]
+pub struct FreeformConvertErr<A: SerdeScheme, B: SerdeScheme> {
+    pub key: String,
+    pub error: ConvertError<A, B>,
 }
 
 impl<S: SerdeScheme> From<&SordError<S>> for FreeformErr<S> {
@@ -64,31 +102,143 @@ impl<S: SerdeScheme> From<SordError<S>> for FreeformErr<S> {
     }
 }
 
+impl<S: SerdeScheme> FreeformErr<S> {
+    /// Attaches the key name to an error from serializing that key's value
+    fn for_key(key: &str, err: SordError<S>) -> Self {
+        match err {
+            SordError::SeDeError(error) => FreeformErr::KeySerdeError {
+                key: key.to_string(),
+                error,
+            },
+            SordError::WrongTypeError => FreeformErr::KeyTypeDoesNotMatch,
+        }
+    }
+}
+
 impl<S: SerdeScheme> Freeform<S> {
     pub fn is_empty(&self) -> bool {
         self.0.is_empty()
     }
 
-    pub fn deserialize(input: &str) -> Result<Self, FreeformErr<S>> {
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn contains_key<T>(&self, key: Key<T>) -> bool {
+        self.0.contains_key(key.name())
+    }
+
+    /// Gets the Sord stored for a key name, without deserializing it
+    pub fn get_sord(&self, name: &str) -> Option<&Sord<S>> {
+        self.0.get(name)
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &str> + '_ {
+        self.0.keys().map(String::as_str)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Sord<S>)> + '_ {
+        self.0.iter().map(|(key, sord)| (key.as_str(), sord))
+    }
+
+    /// Removes the entry for a key, returning it as a Sord if it was present
+    pub fn remove<T>(&mut self, key: Key<T>) -> Option<Sord<S>> {
+        map::remove(&mut self.0, key.name())
+    }
+
+    /// Removes the entry for a key, returning the deserialized value if it was present.
+    ///
+    /// If the entry can't be deserialized as a T, it is left in place.
+    pub fn take<T: FreeformData>(&mut self, key: Key<T>) -> Result<Option<T>, FreeformErr<S>> {
+        let Some(sord) = self.0.get(key.name()) else {
+            return Ok(None);
+        };
+        sord.de::<T>()?;
+        self.remove(key)
+            .map(|sord| sord.into_de::<T>())
+            .transpose()
+            .map_err(FreeformErr::from)
+    }
+
+    /// Keeps only the entries for which `f` returns true
+    pub fn retain<F: FnMut(&str, &mut Sord<S>) -> bool>(&mut self, mut f: F) {
+        self.0.retain(|key, sord| f(key.as_str(), sord))
+    }
+
+    pub fn clear(&mut self) {
+        self.0.clear()
+    }
+
+    pub fn deserialize(input: &S::Se) -> Result<Self, FreeformErr<S>> {
         S::deserialize(input).map_err(FreeformErr::SerdeError)
     }
 
-    pub fn serialize(&self) -> Result<String, FreeformErr<S>> {
+    pub fn serialize(&self) -> Result<S::SeBuf, FreeformErr<S>> {
         S::serialize(self).map_err(FreeformErr::SerdeError)
     }
 
+    /// Serializes the Freeform with every key sorted, including inside nested maps where the
+    /// scheme can see them, so equal content always produces identical output. For [`Json`]
+    /// this is RFC 8785 canonical JSON.
+    ///
+    /// Schemes with opaque values, such as [`Postcard`](crate::Postcard), only sort the top level keys.
+    pub fn serialize_canonical(&self) -> Result<S::SeBuf, FreeformErr<S>> {
+        let entries = self
+            .iter()
+            .map(|(key, sord)| {
+                Ok((
+                    key,
+                    sord.value().map_err(|err| FreeformErr::for_key(key, err))?,
+                ))
+            })
+            .collect::<Result<BTreeMap<_, _>, FreeformErr<S>>>()?;
+        S::serialize_canonical(&entries).map_err(FreeformErr::SerdeError)
+    }
+
+    /// Consumes the Freeform for the scheme value of every entry, collected into any map type.
+    ///
+    /// Fails with [`FreeformErr::KeySerdeError`] naming the first entry that can't be serialized.
+    pub fn try_into_values<M: FromIterator<(String, S::Value)>>(self) -> Result<M, FreeformErr<S>> {
+        self.0
+            .into_iter()
+            .map(|(key, sord)| match sord.into_value() {
+                Ok(val) => Ok((key, val)),
+                Err(err) => Err(FreeformErr::for_key(&key, err)),
+            })
+            .collect()
+    }
+
     pub fn new() -> Self {
         Self::default()
     }
 
     pub fn get_optional<T: FreeformData>(&self, key: Key<T>) -> Result<Option<&T>, FreeformErr<S>> {
-        if let Some(value_sord) = self.0.get(&key.name().to_string()) {
+        if let Some(value_sord) = self.0.get(key.name()) {
             Ok(Some(value_sord.de::<T>()?))
         } else {
             Ok(None)
         }
     }
 
+    /// Gets a value that can borrow from the serialized data of the Freeform, such as a
+    /// `&str`, `Cow<str>` or a struct using `#[serde(borrow)]`, avoiding allocations for
+    /// large payloads. The value is deserialized on every call instead of being cached.
+    ///
+    /// Since `Key` is covariant, a `Key<&'static str>` constant can be used for a `&'a str`.
+    pub fn get_borrowed<'a, T: Deserialize<'a>>(
+        &'a self,
+        key: Key<T>,
+    ) -> Result<Option<T>, FreeformErr<S>>
+    where
+        S: BorrowingSerdeScheme,
+    {
+        if let Some(value_sord) = self.0.get(key.name()) {
+            Ok(Some(value_sord.de_borrowed::<T>()?))
+        } else {
+            Ok(None)
+        }
+    }
+
     pub fn get_owned_or_default<T: FreeformData + ToOwned + Default>(
         &self,
         key: Key<T>,
@@ -108,20 +258,137 @@ impl<S: SerdeScheme> Freeform<S> {
             .map(|opt| opt.cloned().unwrap_or_default())
     }
 
+    /// Gets a value, or the key's own default if it is absent
+    pub fn get<T: FreeformData + Clone>(
+        &self,
+        key: DefaultKey<T>,
+    ) -> Result<Cow<'_, T>, FreeformErr<S>> {
+        Ok(match self.get_optional(key.key())? {
+            Some(value) => Cow::Borrowed(value),
+            None => Cow::Owned(key.default_value()),
+        })
+    }
+
+    /// Gets a copy of an entry as a [`TypedSord`], which keeps the serialized data and only
+    /// deserializes it when needed
+    pub fn get_typed_sord<T: FreeformData + Clone>(
+        &self,
+        key: Key<T>,
+    ) -> Result<Option<TypedSord<T, S>>, FreeformErr<S>> {
+        self.0
+            .get(key.name())
+            .map(|sord| sord.clone().try_typed())
+            .transpose()
+            .map_err(FreeformErr::from)
+    }
+
     pub fn get_required<T: FreeformData>(&self, key: Key<T>) -> Result<&T, FreeformErr<S>> {
-        if let Some(value_sord) = self.0.get(&key.name().to_string()) {
+        if let Some(value_sord) = self.0.get(key.name()) {
             Ok(value_sord.de::<T>()?)
         } else {
             Err(FreeformErr::RequiredKeyNotFound(key.name().to_owned()))
         }
     }
 
+    /// Gets mutable access to a value, deserializing it if needed
+    pub fn get_mut<T: FreeformData>(
+        &mut self,
+        key: Key<T>,
+    ) -> Result<Option<FreeformMut<'_, T>>, FreeformErr<S>> {
+        if let Some(value_sord) = self.0.get_mut(key.name()) {
+            Ok(Some(FreeformMut {
+                de: value_sord.de_mut::<T>()?,
+            }))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Gets the entry for a key for in-place manipulation.
+    ///
+    /// If the key is occupied by a value that doesn't deserialize as a T, the same error as
+    /// [`Freeform::get`] is returned rather than letting it be overwritten:
+    /// [`FreeformErr::KeyTypeDoesNotMatch`] if it was stored as another type, or the scheme's
+    /// error otherwise.
+    pub fn entry<T: FreeformData>(
+        &mut self,
+        key: Key<T>,
+    ) -> Result<Entry<'_, T, S>, FreeformErr<S>> {
+        match self.0.entry(key.name().to_string()) {
+            map::Entry::Occupied(entry) => {
+                entry.get().de::<T>()?;
+                Ok(Entry::Occupied(OccupiedEntry {
+                    entry,
+                    _marker: PhantomData,
+                }))
+            }
+            map::Entry::Vacant(entry) => Ok(Entry::Vacant(VacantEntry {
+                entry,
+                _marker: PhantomData,
+            })),
+        }
+    }
+
+    /// Modifies a value in place if it is present, returning the result of `f`
+    pub fn update<T: FreeformData, R, F: FnOnce(&mut T) -> R>(
+        &mut self,
+        key: Key<T>,
+        f: F,
+    ) -> Result<Option<R>, FreeformErr<S>> {
+        Ok(self.get_mut(key)?.map(|mut value| f(&mut value)))
+    }
+
+    /// Gets a value nested inside of other Freeforms stored in this one
+    pub fn get_path<T: FreeformData>(&self, path: KeyPath<T>) -> Result<Option<&T>, FreeformErr<S>>
+    where
+        Freeform<S>: FreeformData,
+    {
+        let (parents, key) = path.split();
+        let mut current = self;
+        for name in parents {
+            match current.0.get(name) {
+                Some(sord) => current = sord.de::<Freeform<S>>()?,
+                None => return Ok(None),
+            }
+        }
+        current.get_optional(key)
+    }
+
+    /// Puts a value nested inside of other Freeforms stored in this one, creating any of
+    /// those Freeforms that are missing
+    pub fn put_path<T: FreeformData>(
+        &mut self,
+        path: KeyPath<T>,
+        data: T,
+    ) -> Result<(), FreeformErr<S>>
+    where
+        Freeform<S>: FreeformData,
+    {
+        let (parents, key) = path.split();
+        let mut current = self;
+        for name in parents {
+            let parent_key: Key<Freeform<S>> = typed_key!(name);
+            current = current.entry(parent_key)?.or_default()?.de;
+        }
+        current.put(key, data)
+    }
+
     pub fn put<T: FreeformData>(&mut self, key: Key<T>, data: T) -> Result<(), FreeformErr<S>> {
         let sord_data = Sord::from_de::<T>(data);
         self.0.insert(key.name().to_string(), sord_data);
         Ok(())
     }
 
+    /// Puts a [`TypedSord`], keeping whichever forms of the value it has cached
+    pub fn put_typed_sord<T: FreeformData>(
+        &mut self,
+        key: Key<T>,
+        data: TypedSord<T, S>,
+    ) -> Result<(), FreeformErr<S>> {
+        self.0.insert(key.name().to_string(), data.into());
+        Ok(())
+    }
+
     /// Puts a value by ref by serializing and storing that way
     pub fn put_ref<T: FreeformData>(
         &mut self,
@@ -183,22 +450,238 @@ impl<S: SerdeScheme> Freeform<S> {
         }
     }
 
+    /// Re-encodes every entry into another scheme, see [`Sord::convert`]
+    pub fn convert<B: SerdeScheme>(&self) -> Result<Freeform<B>, FreeformConvertErr<S, B>> {
+        self.0
+            .iter()
+            .map(|(key, sord)| {
+                let sord = sord.convert::<B>().map_err(|error| FreeformConvertErr {
+                    key: key.clone(),
+                    error,
+                })?;
+                Ok((key.clone(), sord))
+            })
+            .collect::<Result<_, _>>()
+            .map(Freeform)
+    }
+
+    /// Computes the key-level changes that turn this Freeform into `other`, comparing entries
+    /// the same way as [`PartialEq`]. Operations are sorted by key.
+    pub fn diff(&self, other: &Self) -> Result<FreeformPatch<S>, FreeformErr<S>>
+    where
+        S::Value: PartialEq,
+        S::Se: PartialEq,
+    {
+        let mut keys: Vec<&String> = self
+            .0
+            .keys()
+            .chain(other.0.keys().filter(|key| !self.0.contains_key(*key)))
+            .collect();
+        keys.sort();
+
+        let mut ops = Vec::new();
+        for key in keys {
+            let value = |sord: &Sord<S>| {
+                sord.value()
+                    .cloned()
+                    .map_err(|err| FreeformErr::for_key(key, err))
+            };
+            let op = match (self.0.get(key), other.0.get(key)) {
+                (Some(_), None) => PatchOp::Remove { key: key.clone() },
+                (None, Some(new)) => PatchOp::Add {
+                    key: key.clone(),
+                    value: value(new)?,
+                },
+                (Some(old), Some(new)) => {
+                    if old == new {
+                        continue;
+                    }
+                    PatchOp::Change {
+                        key: key.clone(),
+                        value: value(new)?,
+                    }
+                }
+                (None, None) => continue,
+            };
+            ops.push(op);
+        }
+        Ok(FreeformPatch::new(ops))
+    }
+
+    /// Applies a patch, such as one produced by [`Freeform::diff`]
+    ///
+    /// `Change` and `Remove` operations fail with [`FreeformErr::RequiredKeyNotFound`] if the
+    /// key is missing. If an error is returned, the operations before it remain applied.
+    pub fn apply_patch(&mut self, patch: &FreeformPatch<S>) -> Result<(), FreeformErr<S>> {
+        for op in patch.ops() {
+            match op {
+                PatchOp::Add { key, value } => {
+                    self.0.insert(key.clone(), Sord::from_value(value.clone()));
+                }
+                PatchOp::Change { key, value } => {
+                    let sord = self
+                        .0
+                        .get_mut(key)
+                        .ok_or_else(|| FreeformErr::RequiredKeyNotFound(key.clone()))?;
+                    *sord = Sord::from_value(value.clone());
+                }
+                PatchOp::Remove { key } => {
+                    map::remove(&mut self.0, key)
+                        .ok_or_else(|| FreeformErr::RequiredKeyNotFound(key.clone()))?;
+                }
+            }
+        }
+        Ok(())
+    }
+
     pub fn aggregate<F: IntoIterator<Item = Self>>(freeform: F) -> Option<Self> {
         freeform.into_iter().reduce(|mut acm, effects| {
             acm.extend(effects);
             acm
         })
     }
+
+    /// Aggregates Freeforms, merging each one into the ones before it with a strategy
+    pub fn aggregate_with<F: IntoIterator<Item = Self>>(
+        freeform: F,
+        strategy: &MergeStrategy<S>,
+    ) -> Result<Option<Self>, FreeformErr<S>>
+    where
+        Freeform<S>: FreeformData,
+    {
+        freeform
+            .into_iter()
+            .try_fold(None, |acm: Option<Self>, effects| match acm {
+                None => Ok(Some(effects)),
+                Some(mut acm) => {
+                    acm.merge(effects, strategy)?;
+                    Ok(Some(acm))
+                }
+            })
+    }
+
+    /// Merges another Freeform into this one, resolving keys present in both with a strategy.
+    ///
+    /// If an error is returned, the keys merged before the error remain merged.
+    pub fn merge(&mut self, other: Self, strategy: &MergeStrategy<S>) -> Result<(), FreeformErr<S>>
+    where
+        Freeform<S>: FreeformData,
+    {
+        self.merge_with(other, strategy.policy, Some(&strategy.combiners))
+    }
+
+    fn merge_with(
+        &mut self,
+        other: Self,
+        policy: MergePolicy,
+        combiners: Option<&HashMap<String, Combiner<S>>>,
+    ) -> Result<(), FreeformErr<S>>
+    where
+        Freeform<S>: FreeformData,
+    {
+        for (key, incoming) in other.0 {
+            let Some(existing) = self.0.get_mut(&key) else {
+                self.0.insert(key, incoming);
+                continue;
+            };
+            if let Some(combiner) = combiners.and_then(|combiners| combiners.get(&key)) {
+                combiner(existing, incoming)?;
+                continue;
+            }
+            match policy {
+                MergePolicy::LastWins => *existing = incoming,
+                MergePolicy::FirstWins => {}
+                MergePolicy::ErrorOnConflict => return Err(FreeformErr::KeyConflict(key)),
+                MergePolicy::DeepMerge => {
+                    // Maps are read through their values, so any nested object can be merged
+                    // regardless of what type it has been deserialized as. Opaque values, as
+                    // in postcard, can't be told apart from maps and are only merged if they
+                    // were stored as Freeforms.
+                    let as_freeform = |sord: &Sord<S>| match sord.cached_de::<Freeform<S>>() {
+                        Some(freeform) => Some(freeform.clone()),
+                        None => sord
+                            .value()
+                            .ok()
+                            .filter(|value| S::value_is_map(value))
+                            .and_then(|value| S::deserialize_value::<Freeform<S>>(value).ok()),
+                    };
+                    match (as_freeform(existing), as_freeform(&incoming)) {
+                        (Some(mut existing_map), Some(incoming_map)) => {
+                            existing_map.merge_with(incoming_map, policy, None)?;
+                            *existing = Sord::from_de(existing_map);
+                        }
+                        _ => *existing = incoming,
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Freeforms are equal when they have the same keys with equal [`Sord`]s, so a Freeform with
+/// entries that can't be serialized is still equal to itself and its clones.
+impl<S: SerdeScheme> PartialEq for Freeform<S>
+where
+    S::Value: PartialEq,
+    S::Se: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len()
+            && self
+                .iter()
+                .all(|(key, sord)| other.get_sord(key).is_some_and(|other| sord == other))
+    }
+}
+
+impl<S: SerdeScheme> Eq for Freeform<S>
+where
+    S::Value: Eq,
+    S::Se: Eq,
+{
+}
+
+/// Entries are hashed in key order, so equal Freeforms hash the same however they were built
+impl<S: SerdeScheme> Hash for Freeform<S>
+where
+    S::Value: Hash,
+    S::Se: Hash,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let mut entries: Vec<_> = self.iter().collect();
+        entries.sort_unstable_by_key(|(key, _)| *key);
+        entries.hash(state)
+    }
 }
 
 impl<S: SerdeScheme> IntoIterator for Freeform<S> {
-    type IntoIter = std::collections::hash_map::IntoIter<String, Sord<S>>;
+    type IntoIter = IntoIter<S>;
     type Item = (String, Sord<S>);
     fn into_iter(self) -> Self::IntoIter {
-        self.0.into_iter()
+        IntoIter(self.0.into_iter())
     }
 }
 
+/// An owning iterator over the entries of a [`Freeform`], in insertion order with the
+/// `indexmap` feature and in arbitrary order otherwise
+#[derive(Debug)]
+pub struct IntoIter<S: SerdeScheme>(map::IntoIter<String, Sord<S>>);
+
+impl<S: SerdeScheme> Iterator for IntoIter<S> {
+    type Item = (String, Sord<S>);
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<S: SerdeScheme> ExactSizeIterator for IntoIter<S> {}
+
+impl<S: SerdeScheme> FusedIterator for IntoIter<S> {}
+
 impl<S: SerdeScheme> Extend<(String, Sord<S>)> for Freeform<S> {
     fn extend<T: IntoIterator<Item = (String, Sord<S>)>>(&mut self, iter: T) {
         self.0.extend(iter)
@@ -210,27 +693,80 @@ impl<S: SerdeScheme> TryFrom<HashMap<String, S::Value>> for Freeform<S> {
     fn try_from(map: HashMap<String, S::Value>) -> std::result::Result<Self, Self::Error> {
         let converted_map = map
             .into_iter()
-            .map(|(key, val)| Ok((key, Sord::<S>::from_value(&val)?)))
-            .collect::<std::result::Result<_, Self::Error>>()?;
+            .map(|(key, val)| (key, Sord::<S>::from_value(val)))
+            .collect();
 
         Ok(Freeform(converted_map))
     }
 }
 
-impl<S: SerdeScheme> From<Freeform<S>> for HashMap<String, S::Value> {
-    fn from(metadata: Freeform<S>) -> Self {
-        metadata
-            .0
+impl<S: SerdeScheme> TryFrom<Freeform<S>> for HashMap<String, S::Value> {
+    type Error = FreeformErr<S>;
+    fn try_from(freeform: Freeform<S>) -> std::result::Result<Self, Self::Error> {
+        freeform.try_into_values()
+    }
+}
+
+/// Serialized as a map of each key to its scheme value. An entry that can't be serialized
+/// fails with an error naming its key, instead of panicking.
+impl<S: SerdeScheme> Serialize for Freeform<S> {
+    fn serialize<Ser: serde::Serializer>(
+        &self,
+        serializer: Ser,
+    ) -> std::result::Result<Ser::Ok, Ser::Error> {
+        use serde::ser::{Error, SerializeMap};
+
+        let mut map = serializer.serialize_map(Some(self.len()))?;
+        for (key, sord) in self.iter() {
+            let value = sord
+                .value()
+                .map_err(|err| Ser::Error::custom(FreeformErr::for_key(key, err)))?;
+            map.serialize_entry(key, value)?;
+        }
+        map.end()
+    }
+}
+
+#[cfg(feature = "indexmap")]
+impl<S: SerdeScheme> TryFrom<indexmap::IndexMap<String, S::Value>> for Freeform<S> {
+    type Error = FreeformErr<S>;
+    fn try_from(
+        map: indexmap::IndexMap<String, S::Value>,
+    ) -> std::result::Result<Self, Self::Error> {
+        let converted_map = map
             .into_iter()
-            .map(|(key, val)| (key, val.value().expect("Should be able to serialize")))
-            .collect()
+            .map(|(key, val)| (key, Sord::<S>::from_value(val)))
+            .collect();
+
+        Ok(Freeform(converted_map))
     }
 }
 
-#[cfg(all(test, any(feature = "json", feature = "toml", feature = "ron")))]
+#[cfg(feature = "indexmap")]
+impl<S: SerdeScheme> TryFrom<Freeform<S>> for indexmap::IndexMap<String, S::Value> {
+    type Error = FreeformErr<S>;
+    fn try_from(freeform: Freeform<S>) -> std::result::Result<Self, Self::Error> {
+        freeform.try_into_values()
+    }
+}
+
+#[cfg(all(
+    test,
+    any(
+        feature = "json",
+        feature = "toml",
+        feature = "ron",
+        feature = "yaml",
+        feature = "postcard",
+        feature = "bincode",
+        feature = "cbor",
+        feature = "msgpack"
+    )
+))]
 mod test {
     #[cfg(feature = "json")]
     use serde_json::{Map, Number, Value};
+    use std::borrow::Borrow;
     use std::collections::HashMap;
 
     use typed_key::{typed_key, Key};
@@ -238,7 +774,8 @@ mod test {
     #[cfg(feature = "ron")]
     use crate::scheme::Ron;
 
-    use super::Freeform;
+    use super::{Freeform, FreeformData};
+    use crate::SerdeScheme;
 
     const NUM_KEY: Key<usize> = typed_key!("num");
     const MAP_KEY: Key<HashMap<String, String>> = typed_key!("map");
@@ -283,6 +820,43 @@ mod test {
         assert_eq!(Value::Object(expected_map), result);
     }
 
+    /// JSON stored as bytes, to exercise schemes with binary buffers
+    #[cfg(feature = "json")]
+    #[derive(Clone, Debug, Default)]
+    struct JsonBytes;
+
+    #[cfg(feature = "json")]
+    impl crate::SerdeScheme for JsonBytes {
+        type Error = std::sync::Arc<serde_json::Error>;
+        type Value = serde_json::Value;
+        type Se = [u8];
+        type SeBuf = Vec<u8>;
+        fn deserialize<T: serde::de::DeserializeOwned>(input: &[u8]) -> Result<T, Self::Error> {
+            serde_json::from_slice(input).map_err(std::sync::Arc::new)
+        }
+        fn serialize<T: serde::Serialize>(input: &T) -> Result<Vec<u8>, Self::Error> {
+            serde_json::to_vec(input).map_err(std::sync::Arc::new)
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "json")]
+    pub fn binary_scheme_test() {
+        let mut freeform = <Freeform<JsonBytes>>::new();
+        freeform.put(NUM_KEY, 12).unwrap();
+        freeform.put_ref(MAP_KEY, &test_map()).unwrap();
+
+        let serialized: Vec<u8> = freeform.serialize().unwrap();
+        let deserialized = Freeform::<JsonBytes>::deserialize(&serialized).unwrap();
+        assert_eq!(&12, deserialized.get_required(NUM_KEY).unwrap());
+        assert_eq!(&test_map(), deserialized.get_required(MAP_KEY).unwrap());
+
+        let sord = crate::Sord::<JsonBytes>::from_de(5usize);
+        assert_eq!(b"5".as_slice(), sord.se::<usize>().unwrap());
+        let typed = crate::TypedSord::<usize, JsonBytes>::from_se(b"7".as_slice());
+        assert_eq!(&7, typed.de().unwrap());
+    }
+
     #[test]
     #[cfg(feature = "ron")]
     pub fn ron_test() {
@@ -339,4 +913,562 @@ mod test {
         assert_eq!(Some(&143), inner_freeform.get_optional(NUM_KEY).unwrap());
         assert_eq!(None, inner_freeform.get_optional(MAP_KEY).unwrap());
     }
+
+    /// Serializes a Freeform with a nested Freeform through the scheme and reads it back
+    #[allow(dead_code)]
+    fn round_trip_test<S: SerdeScheme>()
+    where
+        Freeform<S>: FreeformData,
+    {
+        let ff_key: Key<Freeform<S>> = typed_key!("ff");
+        let mut freeform = <Freeform<S>>::new();
+        freeform.put(NUM_KEY, 62).unwrap();
+        freeform.put(MAP_KEY, test_map()).unwrap();
+        let mut ff: Freeform<S> = Freeform::new();
+        ff.put(NUM_KEY, 143).unwrap();
+        freeform.put(ff_key, ff).unwrap();
+
+        let serialized = freeform.serialize().unwrap();
+        let deserialized = Freeform::<S>::deserialize(serialized.borrow()).unwrap();
+
+        assert_eq!(&62, deserialized.get_required(NUM_KEY).unwrap());
+        assert_eq!(&test_map(), deserialized.get_required(MAP_KEY).unwrap());
+        let inner_freeform = deserialized.get_required(ff_key).unwrap();
+        assert_eq!(Some(&143), inner_freeform.get_optional(NUM_KEY).unwrap());
+        assert_eq!(None, inner_freeform.get_optional(MAP_KEY).unwrap());
+    }
+
+    #[test]
+    #[cfg(feature = "json")]
+    pub fn map_api_test() {
+        let mut freeform = <Freeform>::new();
+        let missing_key: Key<String> = typed_key!("missing");
+        freeform.put(NUM_KEY, 343).unwrap();
+        freeform.put(MAP_KEY, test_map()).unwrap();
+        freeform
+            .put(missing_key, "not missing yet".to_string())
+            .unwrap();
+        assert_eq!(3, freeform.len());
+        assert!(freeform.contains_key(missing_key));
+
+        freeform.retain(|key, _| key != missing_key.name());
+        assert!(!freeform.contains_key(missing_key));
+
+        let mut keys: Vec<&str> = freeform.keys().collect();
+        keys.sort();
+        assert_eq!(vec![MAP_KEY.name(), NUM_KEY.name()], keys);
+        assert_eq!(2, freeform.iter().count());
+
+        let wrong_type_key: Key<String> = typed_key!("num");
+        assert!(freeform.take(wrong_type_key).is_err());
+        assert!(freeform.contains_key(NUM_KEY));
+
+        assert_eq!(Some(test_map()), freeform.take(MAP_KEY).unwrap());
+        assert_eq!(None, freeform.take(MAP_KEY).unwrap());
+        assert!(freeform.remove(NUM_KEY).is_some());
+        assert!(freeform.is_empty());
+
+        // The deserialized value is shared between clones
+        freeform.put(NUM_KEY, 4).unwrap();
+        let cloned = freeform.clone();
+        assert_eq!(Some(4), freeform.take(NUM_KEY).unwrap());
+        assert_eq!(&4, cloned.get_required(NUM_KEY).unwrap());
+        freeform.clear();
+        assert!(freeform.is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "json")]
+    pub fn get_mut_test() {
+        let mut freeform = <Freeform>::deserialize("{\"num\":4}").unwrap();
+        freeform.put(MAP_KEY, test_map()).unwrap();
+        let cloned = freeform.clone();
+
+        *freeform.get_mut(NUM_KEY).unwrap().unwrap() += 1;
+        freeform
+            .get_mut(MAP_KEY)
+            .unwrap()
+            .unwrap()
+            .insert("new".to_string(), "NEW".to_string());
+        let removed = freeform
+            .update(MAP_KEY, |map| map.remove("foo"))
+            .unwrap()
+            .flatten();
+        assert_eq!(Some("FOO".to_string()), removed);
+        assert_eq!(
+            None,
+            freeform
+                .update(typed_key!("missing"), |n: &mut u8| *n)
+                .unwrap()
+        );
+
+        let reserialized = <Freeform>::deserialize(&freeform.serialize().unwrap()).unwrap();
+        let mut expected_map = test_map();
+        expected_map.remove("foo");
+        expected_map.insert("new".to_string(), "NEW".to_string());
+        assert_eq!(&5, reserialized.get_required(NUM_KEY).unwrap());
+        assert_eq!(&expected_map, reserialized.get_required(MAP_KEY).unwrap());
+
+        // Clones aren't affected
+        assert_eq!(&4, cloned.get_required(NUM_KEY).unwrap());
+        assert_eq!(&test_map(), cloned.get_required(MAP_KEY).unwrap());
+    }
+
+    #[test]
+    #[cfg(feature = "json")]
+    pub fn entry_test() {
+        let list_key: Key<Vec<String>> = typed_key!("list");
+        let mut freeform = <Freeform>::deserialize("{\"num\":4}").unwrap();
+        let mismatched_key: Key<String> = typed_key!("num");
+        assert!(matches!(
+            freeform.entry(mismatched_key),
+            Err(crate::FreeformErr::SerdeError(_))
+        ));
+        assert!(matches!(
+            freeform.take(mismatched_key),
+            Err(crate::FreeformErr::SerdeError(_))
+        ));
+
+        *freeform.entry(NUM_KEY).unwrap().or_insert(0).unwrap() += 1;
+        freeform
+            .entry(list_key)
+            .unwrap()
+            .or_default()
+            .unwrap()
+            .push("first".to_string());
+        freeform
+            .entry(list_key)
+            .unwrap()
+            .and_modify(|list| list.push("second".to_string()))
+            .unwrap()
+            .or_default()
+            .unwrap();
+
+        assert_eq!(&5, freeform.get_required(NUM_KEY).unwrap());
+        assert_eq!(
+            &vec!["first".to_string(), "second".to_string()],
+            freeform.get_required(list_key).unwrap()
+        );
+
+        // The value has been put as a usize now
+        assert!(matches!(
+            freeform.entry(mismatched_key),
+            Err(crate::FreeformErr::KeyTypeDoesNotMatch)
+        ));
+        assert!(matches!(
+            freeform.get_required(mismatched_key),
+            Err(crate::FreeformErr::KeyTypeDoesNotMatch)
+        ));
+        assert_eq!(&5, freeform.get_required(NUM_KEY).unwrap());
+
+        match freeform.entry(NUM_KEY).unwrap() {
+            crate::Entry::Occupied(entry) => assert_eq!(5, entry.remove().unwrap()),
+            crate::Entry::Vacant(_) => panic!("num should be occupied"),
+        }
+        assert!(!freeform.contains_key(NUM_KEY));
+    }
+
+    #[test]
+    #[cfg(feature = "json")]
+    pub fn key_path_test() {
+        use crate::KeyPath;
+
+        const PORT: KeyPath<u16> = KeyPath::new("server.http.port");
+        const HOST: KeyPath<String> = KeyPath::new("server.host");
+        const TOP: KeyPath<usize> = KeyPath::new("num");
+
+        let mut freeform =
+            <Freeform>::deserialize("{\"num\":3,\"server\":{\"host\":\"localhost\"}}").unwrap();
+        assert_eq!(Some(&3), freeform.get_path(TOP).unwrap());
+        assert_eq!(
+            Some(&"localhost".to_string()),
+            freeform.get_path(HOST).unwrap()
+        );
+        assert_eq!(None, freeform.get_path(PORT).unwrap());
+
+        freeform.put_path(PORT, 8080).unwrap();
+        freeform.put_path(KeyPath::new("other.flag"), true).unwrap();
+        assert_eq!(Some(&8080), freeform.get_path(PORT).unwrap());
+        assert_eq!(
+            Some(&"localhost".to_string()),
+            freeform.get_path(HOST).unwrap()
+        );
+
+        let value = serde_json::to_value(&freeform).unwrap();
+        assert_eq!(
+            serde_json::json!({
+                "num": 3,
+                "server": {"host": "localhost", "http": {"port": 8080}},
+                "other": {"flag": true}
+            }),
+            value
+        );
+
+        assert!(freeform.put_path(KeyPath::new("num.inner"), 1).is_err());
+    }
+
+    #[test]
+    pub fn key_path_empty_names_test() {
+        use crate::KeyPath;
+
+        for path in ["", "a..b", ".a", "a."] {
+            let result = std::panic::catch_unwind(|| KeyPath::<usize>::new(path));
+            assert!(result.is_err(), "{path:?} should be rejected");
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "json")]
+    pub fn default_key_test() {
+        use crate::DefaultKey;
+
+        const RETRIES: DefaultKey<u32> = DefaultKey::new(typed_key!("retries"), || 3);
+        const NAMES: DefaultKey<Vec<String>> = DefaultKey::new(typed_key!("names"), Vec::new);
+
+        let mut freeform = <Freeform>::new();
+        assert_eq!(3, *freeform.get(RETRIES).unwrap());
+        assert!(freeform.get(NAMES).unwrap().is_empty());
+
+        freeform.put(RETRIES.into(), 5).unwrap();
+        assert_eq!(5, *freeform.get(RETRIES).unwrap());
+        assert_eq!(&5, freeform.get_required(RETRIES.key()).unwrap());
+    }
+
+    #[test]
+    #[cfg(feature = "json")]
+    pub fn merge_test() {
+        use crate::{FreeformErr, MergePolicy, MergeStrategy};
+
+        let list_key: Key<Vec<String>> = typed_key!("list");
+        let nested_key: Key<Freeform> = typed_key!("nested");
+        let first = <Freeform>::deserialize(
+            "{\"num\":1,\"list\":[\"a\"],\"nested\":{\"x\":1,\"y\":{\"z\":1}},\"only_first\":true}",
+        )
+        .unwrap();
+        let second = <Freeform>::deserialize(
+            "{\"num\":2,\"list\":[\"b\"],\"nested\":{\"y\":{\"w\":2}},\"only_second\":true}",
+        )
+        .unwrap();
+
+        let merge = |policy| {
+            let mut strategy = MergeStrategy::new(policy);
+            strategy.combine(list_key, |list, other| list.extend(other));
+            Freeform::aggregate_with([first.clone(), second.clone()], &strategy)
+        };
+
+        let last_wins = merge(MergePolicy::LastWins).unwrap().unwrap();
+        assert_eq!(&2, last_wins.get_required(NUM_KEY).unwrap());
+        assert_eq!(
+            &vec!["a".to_string(), "b".to_string()],
+            last_wins.get_required(list_key).unwrap()
+        );
+        assert_eq!(
+            serde_json::json!({"y": {"w": 2}}),
+            serde_json::to_value(last_wins.get_required(nested_key).unwrap()).unwrap()
+        );
+        assert_eq!(5, last_wins.len());
+
+        let first_wins = merge(MergePolicy::FirstWins).unwrap().unwrap();
+        assert_eq!(&1, first_wins.get_required(NUM_KEY).unwrap());
+
+        let deep = merge(MergePolicy::DeepMerge).unwrap().unwrap();
+        assert_eq!(&2, deep.get_required(NUM_KEY).unwrap());
+        assert_eq!(
+            serde_json::json!({"x": 1, "y": {"z": 1, "w": 2}}),
+            serde_json::to_value(deep.get_required(nested_key).unwrap()).unwrap()
+        );
+
+        assert!(matches!(
+            merge(MergePolicy::ErrorOnConflict),
+            Err(FreeformErr::KeyConflict(_))
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "json")]
+    pub fn eq_test() {
+        use std::collections::HashSet;
+
+        let mut built = <Freeform>::new();
+        built.put(NUM_KEY, 3).unwrap();
+        built
+            .put(typed_key!("name"), "freeform".to_string())
+            .unwrap();
+        let parsed = <Freeform>::deserialize("{\"name\":\"freeform\",\"num\":3}").unwrap();
+        assert_eq!(built, parsed);
+
+        // The lazily filled caches don't affect the hash, so this is fine as a key
+        #[allow(clippy::mutable_key_type)]
+        let mut cache = HashSet::new();
+        cache.insert(built.clone());
+        assert!(cache.contains(&parsed));
+
+        built.put(NUM_KEY, 4).unwrap();
+        assert_ne!(built, parsed);
+        assert!(!cache.contains(&built));
+    }
+
+    #[test]
+    #[cfg(all(feature = "json", feature = "indexmap"))]
+    pub fn insertion_order_test() {
+        let input = "{\"zebra\":1,\"apple\":{\"y\":2,\"x\":3},\"mango\":4}";
+        let mut freeform = <Freeform>::deserialize(input).unwrap();
+        assert_eq!(input, freeform.serialize().unwrap());
+
+        freeform.put(typed_key!("banana"), 5).unwrap();
+        freeform.remove(typed_key!("apple") as Key<usize>);
+        assert_eq!(
+            vec!["zebra", "mango", "banana"],
+            freeform.keys().collect::<Vec<_>>()
+        );
+        let entries = freeform.clone().into_iter();
+        assert_eq!(3, entries.len());
+        assert_eq!(
+            vec!["zebra", "mango", "banana"],
+            entries.map(|(key, _)| key).collect::<Vec<_>>()
+        );
+
+        let map: indexmap::IndexMap<String, serde_json::Value> =
+            freeform.clone().try_into().unwrap();
+        assert_eq!(freeform, Freeform::try_from(map).unwrap());
+    }
+
+    #[test]
+    #[cfg(feature = "json")]
+    pub fn canonical_test() {
+        let mut first = <Freeform>::new();
+        first.put(MAP_KEY, test_map()).unwrap();
+        first.put(NUM_KEY, 3).unwrap();
+        let second = <Freeform>::deserialize(
+            "{\"num\":3.0,\"map\":{\"hello\":\"bonjour\",\"foo\":\"FOO\",\"bar\":\"BAR\"}}",
+        )
+        .unwrap();
+        let canonical = first.serialize_canonical().unwrap();
+        assert_eq!(
+            "{\"map\":{\"bar\":\"BAR\",\"foo\":\"FOO\",\"hello\":\"bonjour\"},\"num\":3}",
+            canonical
+        );
+        assert_eq!(canonical, second.serialize_canonical().unwrap());
+    }
+
+    #[test]
+    #[cfg(feature = "json")]
+    pub fn unserializable_test() {
+        // Json can't serialize maps with non-string keys
+        let bad_key: Key<HashMap<(i32, i32), i32>> = typed_key!("bad");
+        let mut freeform = <Freeform>::new();
+        freeform.put(NUM_KEY, 3).unwrap();
+        freeform.put(bad_key, HashMap::from([((1, 2), 3)])).unwrap();
+
+        let err = serde_json::to_string(&freeform).unwrap_err();
+        assert!(err.to_string().contains("[bad]"), "{err}");
+        assert!(freeform.serialize().is_err());
+        assert!(matches!(
+            freeform.serialize_canonical(),
+            Err(super::FreeformErr::KeySerdeError { key, .. }) if key == "bad"
+        ));
+
+        // Equality is still reflexive, so it can be looked up in a set
+        assert_eq!(freeform, freeform.clone());
+        #[allow(clippy::mutable_key_type)]
+        let set = std::collections::HashSet::from([freeform.clone()]);
+        assert!(set.contains(&freeform));
+        let mut other = freeform.clone();
+        other.put(bad_key, HashMap::from([((4, 5), 6)])).unwrap();
+        assert!(!set.contains(&other));
+        assert!(matches!(
+            freeform.try_into_values::<HashMap<_, _>>(),
+            Err(super::FreeformErr::KeySerdeError { key, .. }) if key == "bad"
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "cbor")]
+    pub fn cbor_canonical_test() {
+        let mut first = Freeform::<crate::Cbor>::new();
+        first.put(MAP_KEY, test_map()).unwrap();
+        first.put(NUM_KEY, 3).unwrap();
+        let mut second = Freeform::<crate::Cbor>::new();
+        second.put(NUM_KEY, 3).unwrap();
+        second.put(MAP_KEY, test_map()).unwrap();
+        let canonical = first.serialize_canonical().unwrap();
+        assert_eq!(canonical, second.serialize_canonical().unwrap());
+        assert_eq!(first, Freeform::deserialize(&canonical[..]).unwrap());
+    }
+
+    #[test]
+    #[cfg(feature = "json")]
+    pub fn borrowed_test() {
+        use serde::{Deserialize, Serialize};
+        use std::borrow::Cow;
+
+        #[derive(Debug, Deserialize, Serialize, PartialEq)]
+        struct Blob<'a> {
+            #[serde(borrow)]
+            text: Cow<'a, str>,
+            count: u8,
+        }
+
+        const TEXT_KEY: Key<&str> = typed_key!("text");
+        const ESCAPED_KEY: Key<Cow<str>> = typed_key!("escaped");
+        const BLOB_KEY: Key<Blob> = typed_key!("blob");
+
+        let freeform = <Freeform>::deserialize(
+            "{\"text\":\"big text\",\"escaped\":\"a\\nb\",\"blob\":{\"text\":\"inner\",\"count\":3}}",
+        )
+        .unwrap();
+
+        assert_eq!(Some("big text"), freeform.get_borrowed(TEXT_KEY).unwrap());
+        assert_eq!(
+            Some(Cow::Owned::<str>("a\nb".to_string())),
+            freeform.get_borrowed(ESCAPED_KEY).unwrap()
+        );
+        let blob = freeform.get_borrowed(BLOB_KEY).unwrap().unwrap();
+        assert!(matches!(blob.text, Cow::Borrowed("inner")));
+        assert_eq!(3, blob.count);
+        assert_eq!(
+            None,
+            freeform.get_borrowed(typed_key!("missing")).unwrap() as Option<&str>
+        );
+
+        let mut freeform = <Freeform>::new();
+        freeform.put(NUM_KEY, 5).unwrap();
+        let num: Option<u64> = freeform.get_borrowed(typed_key!("num")).unwrap();
+        assert_eq!(Some(5), num);
+    }
+
+    #[test]
+    #[cfg(all(feature = "json", feature = "ron", feature = "toml"))]
+    pub fn convert_test() {
+        let mut freeform = <Freeform>::deserialize("{\"num\":7}").unwrap();
+        freeform.put(MAP_KEY, test_map()).unwrap();
+
+        let converted = freeform.convert::<Ron>().unwrap();
+        assert_eq!(&7, converted.get_required(NUM_KEY).unwrap());
+        assert_eq!(&test_map(), converted.get_required(MAP_KEY).unwrap());
+        let ron_value: ron::Value = ron::from_str(&converted.serialize().unwrap()).unwrap();
+        let json_value: ron::Value = ron::from_str(&freeform.serialize().unwrap()).unwrap();
+        assert_eq!(json_value, ron_value);
+
+        // Reading a type that skips some of the data shouldn't lose it when converting
+        #[derive(serde::Deserialize, serde::Serialize)]
+        struct Partial {
+            name: String,
+        }
+        let partial_key: Key<Partial> = typed_key!("p");
+        let freeform = <Freeform>::deserialize("{\"p\":{\"name\":\"x\",\"extra\":2}}").unwrap();
+        assert_eq!("x", freeform.get_required(partial_key).unwrap().name);
+        let converted = freeform.convert::<crate::Toml>().unwrap();
+        let nested_key: Key<Freeform<crate::Toml>> = typed_key!("p");
+        let extra_key: Key<usize> = typed_key!("extra");
+        let nested = converted.get_required(nested_key).unwrap();
+        assert_eq!(&2, nested.get_required(extra_key).unwrap());
+
+        // Toml has no null value, so the empty key should be reported
+        let mut freeform = <Freeform>::deserialize("{\"num\":7}").unwrap();
+        let empty_key: Key<Option<usize>> = typed_key!("empty");
+        freeform.put(empty_key, None).unwrap();
+        let err = freeform.convert::<crate::Toml>().unwrap_err();
+        assert_eq!(empty_key.name(), err.key);
+        assert!(matches!(err.error, crate::ConvertError::Target(_)));
+    }
+
+    #[test]
+    #[cfg(feature = "yaml")]
+    pub fn yaml_test() {
+        round_trip_test::<crate::Yaml>();
+
+        let manifest = "num: 4\nmap:\n  foo: FOO\n  bar: BAR\n  hello: bonjour\n";
+        let freeform = Freeform::<crate::Yaml>::deserialize(manifest).unwrap();
+        assert_eq!(&4, freeform.get_required(NUM_KEY).unwrap());
+        assert_eq!(&test_map(), freeform.get_required(MAP_KEY).unwrap());
+    }
+
+    #[test]
+    #[cfg(feature = "postcard")]
+    pub fn postcard_merge_test() {
+        use crate::{MergePolicy, MergeStrategy, Postcard};
+
+        // Opaque bytes can happen to decode as a map, so they shouldn't be deep merged
+        let mut first = Freeform::<Postcard>::new();
+        first.put(typed_key!("tuple"), (1u8, 0u8, 0u8)).unwrap();
+        let mut second = Freeform::<Postcard>::new();
+        let tuple_key: Key<(u8, u8, u8, u8)> = typed_key!("tuple");
+        second.put(tuple_key, (1, 1, 65, 0)).unwrap();
+
+        let mut merged = first.clone();
+        merged
+            .merge(second.clone(), &MergeStrategy::new(MergePolicy::DeepMerge))
+            .unwrap();
+        assert_eq!(&(1, 1, 65, 0), merged.get_required(tuple_key).unwrap());
+
+        // Values stored as Freeforms can still be merged
+        let nested_key: Key<Freeform<Postcard>> = typed_key!("nested");
+        let mut nested = Freeform::<Postcard>::new();
+        nested.put(NUM_KEY, 1).unwrap();
+        first.put(nested_key, nested).unwrap();
+        let mut nested = Freeform::<Postcard>::new();
+        nested.put(MAP_KEY, test_map()).unwrap();
+        second.put(nested_key, nested).unwrap();
+
+        let mut merged = first.clone();
+        merged
+            .merge(second.clone(), &MergeStrategy::new(MergePolicy::DeepMerge))
+            .unwrap();
+        let nested = merged.get_required(nested_key).unwrap();
+        assert_eq!(&1, nested.get_required(NUM_KEY).unwrap());
+        assert_eq!(&test_map(), nested.get_required(MAP_KEY).unwrap());
+
+        // But once loaded, they are opaque bytes again and are replaced
+        let mut merged =
+            Freeform::<Postcard>::deserialize(&first.serialize().unwrap()[..]).unwrap();
+        merged
+            .merge(second, &MergeStrategy::new(MergePolicy::DeepMerge))
+            .unwrap();
+        let nested = merged.get_required(nested_key).unwrap();
+        assert_eq!(&test_map(), nested.get_required(MAP_KEY).unwrap());
+        assert!(!nested.contains_key(NUM_KEY));
+        assert_eq!(&(1, 1, 65, 0), merged.get_required(tuple_key).unwrap());
+    }
+
+    #[test]
+    #[cfg(feature = "postcard")]
+    pub fn postcard_test() {
+        round_trip_test::<crate::Postcard>();
+    }
+
+    #[test]
+    #[cfg(all(feature = "postcard", feature = "json"))]
+    pub fn postcard_convert_test() {
+        let num_key: Key<u32> = typed_key!("n");
+        let mut freeform = Freeform::<crate::Postcard>::new();
+        freeform.put(num_key, 300).unwrap();
+        let converted = freeform.convert::<crate::Json>().unwrap();
+        assert_eq!("{\"n\":300}", converted.serialize().unwrap());
+
+        // Once loaded, postcard's bytes can't be read without knowing their type
+        let loaded =
+            Freeform::<crate::Postcard>::deserialize(&freeform.serialize().unwrap()[..]).unwrap();
+        let err = loaded.convert::<crate::Json>().unwrap_err();
+        assert_eq!("n", err.key);
+        assert!(matches!(err.error, crate::ConvertError::Source(_)));
+    }
+
+    #[test]
+    #[cfg(feature = "bincode")]
+    pub fn bincode_test() {
+        round_trip_test::<crate::Bincode>();
+    }
+
+    #[test]
+    #[cfg(feature = "cbor")]
+    pub fn cbor_test() {
+        round_trip_test::<crate::Cbor>();
+    }
+
+    #[test]
+    #[cfg(feature = "msgpack")]
+    pub fn msgpack_test() {
+        round_trip_test::<crate::MessagePack>();
+    }
 }
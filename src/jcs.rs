@@ -0,0 +1,162 @@
+//! Canonical JSON as described by RFC 8785, the JSON Canonicalization Scheme
+
+use std::cmp::Ordering;
+
+use serde_json::Value;
+
+/// Writes a value as canonical JSON: no whitespace, object members sorted by the UTF-16 code
+/// units of their names, and numbers formatted like ECMAScript's `Number.prototype.toString`
+pub(crate) fn to_string(value: &Value) -> String {
+    let mut out = String::new();
+    write_value(&mut out, value);
+    out
+}
+
+fn write_value(out: &mut String, value: &Value) {
+    match value {
+        Value::Null => out.push_str("null"),
+        Value::Bool(b) => out.push_str(if *b { "true" } else { "false" }),
+        Value::Number(n) => {
+            // Every JSON number is an IEEE 754 double under RFC 8785, even large integers
+            write_number(out, n.as_f64().unwrap_or_default())
+        }
+        Value::String(s) => write_string(out, s),
+        Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_value(out, item);
+            }
+            out.push(']');
+        }
+        Value::Object(map) => {
+            let mut members: Vec<_> = map.iter().collect();
+            members.sort_by(|(a, _), (b, _)| cmp_utf16(a, b));
+            out.push('{');
+            for (i, (key, item)) in members.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_string(out, key);
+                out.push(':');
+                write_value(out, item);
+            }
+            out.push('}');
+        }
+    }
+}
+
+fn cmp_utf16(a: &str, b: &str) -> Ordering {
+    a.encode_utf16().cmp(b.encode_utf16())
+}
+
+// serde_json already escapes strings the way RFC 8785 requires
+fn write_string(out: &mut String, s: &str) {
+    out.push_str(&serde_json::to_string(s).expect("strings always serialize"));
+}
+
+fn write_number(out: &mut String, n: f64) {
+    if n == 0.0 {
+        out.push('0');
+        return;
+    }
+    if n < 0.0 {
+        out.push('-');
+    }
+    // Rust's exponential formatting gives the shortest digits that round trip, which is
+    // what ECMAScript uses too. Only where the decimal point goes differs.
+    let formatted = format!("{:e}", n.abs());
+    let (mantissa, exponent) = formatted
+        .split_once('e')
+        .expect("exponential format has an exponent");
+    let digits = mantissa.replace('.', "");
+    let exponent: i32 = exponent.parse().expect("exponent is an integer");
+    let len = digits.len() as i32;
+    // The position of the decimal point relative to the start of the digits
+    let point = exponent + 1;
+
+    if len <= point && point <= 21 {
+        out.push_str(&digits);
+        out.push_str(&"0".repeat((point - len) as usize));
+    } else if 0 < point && point <= 21 {
+        let (int, frac) = digits.split_at(point as usize);
+        out.push_str(int);
+        out.push('.');
+        out.push_str(frac);
+    } else if -6 < point && point <= 0 {
+        out.push_str("0.");
+        out.push_str(&"0".repeat(-point as usize));
+        out.push_str(&digits);
+    } else {
+        let (first, rest) = digits.split_at(1);
+        out.push_str(first);
+        if !rest.is_empty() {
+            out.push('.');
+            out.push_str(rest);
+        }
+        out.push('e');
+        out.push(if exponent < 0 { '-' } else { '+' });
+        out.push_str(&exponent.abs().to_string());
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn number_test() {
+        // Examples from RFC 8785 appendix B
+        let cases = [
+            (0.0, "0"),
+            (-0.0, "0"),
+            (5e-324, "5e-324"),
+            (-5e-324, "-5e-324"),
+            (1.7976931348623157e308, "1.7976931348623157e+308"),
+            (9007199254740992.0, "9007199254740992"),
+            (-9007199254740992.0, "-9007199254740992"),
+            (295147905179352830000.0, "295147905179352830000"),
+            (1e21, "1e+21"),
+            (9.999999999999997e22, "9.999999999999997e+22"),
+            (1e23, "1e+23"),
+            (0.000001, "0.000001"),
+            (1e-7, "1e-7"),
+            (333333333.3333333, "333333333.3333333"),
+            (1.5, "1.5"),
+            (100.0, "100"),
+        ];
+        for (n, expected) in cases {
+            let mut out = String::new();
+            write_number(&mut out, n);
+            assert_eq!(expected, out, "formatting {n:e}");
+        }
+    }
+
+    #[test]
+    fn canonical_test() {
+        // The example from RFC 8785 section 3.2.2, except for the first number, which
+        // serde_json only parses exactly with its `float_roundtrip` feature
+        let value: Value = serde_json::from_str(
+            r#"{
+                "numbers": [333333333.3333333, 1E30, 4.50, 2e-3, 0.000000000000000000000000001],
+                "string": "\u20ac$\u000F\u000aA'\u0042\u0022\u005c\\\"\/",
+                "literals": [null, true, false]
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            r#"{"literals":[null,true,false],"numbers":[333333333.3333333,1e+30,4.5,0.002,1e-27],"string":"€$\u000f\nA'B\"\\\\\"/"}"#,
+            to_string(&value)
+        );
+
+        // Sorting uses UTF-16 code units, so characters outside the BMP sort before U+E000+
+        let value =
+            serde_json::json!({"\u{fb33}": 1, "\u{1f600}": 2, "\r": 3, "1": 4, "\u{80}": 5});
+        assert_eq!(
+            "{\"\\r\":3,\"1\":4,\"\u{80}\":5,\"\u{1f600}\":2,\"\u{fb33}\":1}",
+            to_string(&value)
+        );
+    }
+}
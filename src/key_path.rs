@@ -0,0 +1,74 @@
+use std::fmt;
+use std::marker::PhantomData;
+
+use typed_key::{typed_key, Key};
+
+/// A key to a value nested inside of other Freeforms, written as key names separated by
+/// dots, such as `KeyPath::new("server.http.port")`
+///
+/// Since `.` always separates names, keys whose names contain a `.` can't be reached with a
+/// KeyPath.
+pub struct KeyPath<T> {
+    path: &'static str,
+    _marker: PhantomData<T>,
+}
+
+impl<T> KeyPath<T> {
+    /// # Panics
+    ///
+    /// Panics if any of the names in the path is empty, such as in `"a..b"` or `".a"`. When
+    /// the KeyPath is a `const`, this is caught at compile time.
+    pub const fn new(path: &'static str) -> Self {
+        let bytes = path.as_bytes();
+        let mut i = 0;
+        while i <= bytes.len() {
+            let at_start = i == 0 || bytes[i - 1] == b'.';
+            let at_end = i == bytes.len() || bytes[i] == b'.';
+            assert!(!(at_start && at_end), "key paths can't have empty names");
+            i += 1;
+        }
+        KeyPath {
+            path,
+            _marker: PhantomData,
+        }
+    }
+
+    /// The full dotted path
+    pub fn path(&self) -> &'static str {
+        self.path
+    }
+
+    /// Splits the path into the names of the Freeforms it passes through and the key
+    /// for the value at the end of it
+    pub(crate) fn split(&self) -> (impl Iterator<Item = &'static str>, Key<T>) {
+        let (parents, name) = match self.path.rsplit_once('.') {
+            Some((parents, name)) => (Some(parents), name),
+            None => (None, self.path),
+        };
+        (
+            parents.into_iter().flat_map(|parents| parents.split('.')),
+            typed_key!(name),
+        )
+    }
+}
+
+impl<T> Copy for KeyPath<T> {}
+
+impl<T> Clone for KeyPath<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> fmt::Debug for KeyPath<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "KeyPath({:?})", self.path)
+    }
+}
+
+/// Panics if the key's name is empty. A name containing `.` is read as a path.
+impl<T> From<Key<T>> for KeyPath<T> {
+    fn from(key: Key<T>) -> Self {
+        KeyPath::new(key.name())
+    }
+}
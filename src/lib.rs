@@ -1,9 +1,24 @@
+mod default_key;
+mod entry;
 mod freeform;
+#[cfg(feature = "json")]
+mod jcs;
+mod key_path;
+mod map;
+mod merge;
+mod patch;
+mod registry;
 mod scheme;
 mod sord;
 mod typed_sord;
 
+pub use default_key::*;
+pub use entry::*;
 pub use freeform::*;
+pub use key_path::*;
+pub use merge::*;
+pub use patch::*;
+pub use registry::*;
 pub use scheme::*;
 pub use sord::*;
 pub use typed_sord::*;
@@ -21,3 +36,23 @@ pub type FreeformToml = Freeform<Toml>;
 /// Simple alias for Freeform<Ron>
 #[cfg(feature = "ron")]
 pub type FreeformRon = Freeform<Ron>;
+
+/// Simple alias for Freeform<Yaml>
+#[cfg(feature = "yaml")]
+pub type FreeformYaml = Freeform<Yaml>;
+
+/// Simple alias for Freeform<Postcard>
+#[cfg(feature = "postcard")]
+pub type FreeformPostcard = Freeform<Postcard>;
+
+/// Simple alias for Freeform<Bincode>
+#[cfg(feature = "bincode")]
+pub type FreeformBincode = Freeform<Bincode>;
+
+/// Simple alias for Freeform<Cbor>
+#[cfg(feature = "cbor")]
+pub type FreeformCbor = Freeform<Cbor>;
+
+/// Simple alias for Freeform<MessagePack>
+#[cfg(feature = "msgpack")]
+pub type FreeformMessagePack = Freeform<MessagePack>;
@@ -0,0 +1,30 @@
+//! The map backing a Freeform, which keeps insertion order with the `indexmap` feature
+
+#[cfg(feature = "indexmap")]
+pub(crate) use indexmap::map::{Entry, IndexMap as Map, IntoIter, OccupiedEntry, VacantEntry};
+#[cfg(not(feature = "indexmap"))]
+pub(crate) use std::collections::hash_map::{
+    Entry, HashMap as Map, IntoIter, OccupiedEntry, VacantEntry,
+};
+
+/// Removes a key without disturbing the order of the remaining keys
+#[cfg(feature = "indexmap")]
+pub(crate) fn remove<V>(map: &mut Map<String, V>, key: &str) -> Option<V> {
+    map.shift_remove(key)
+}
+
+#[cfg(not(feature = "indexmap"))]
+pub(crate) fn remove<V>(map: &mut Map<String, V>, key: &str) -> Option<V> {
+    map.remove(key)
+}
+
+/// Removes an occupied entry without disturbing the order of the remaining keys
+#[cfg(feature = "indexmap")]
+pub(crate) fn remove_entry<V>(entry: OccupiedEntry<'_, String, V>) -> V {
+    entry.shift_remove()
+}
+
+#[cfg(not(feature = "indexmap"))]
+pub(crate) fn remove_entry<V>(entry: OccupiedEntry<'_, String, V>) -> V {
+    entry.remove()
+}
@@ -0,0 +1,94 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+use typed_key::Key;
+
+#[cfg(feature = "json")]
+use crate::Json;
+use crate::{FreeformData, FreeformErr, SerdeScheme, Sord};
+
+/// How a key present in both Freeforms is resolved when merging them
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MergePolicy {
+    /// The value being merged in replaces the existing one
+    #[default]
+    LastWins,
+    /// The existing value is kept
+    FirstWins,
+    /// Merging fails with [`FreeformErr::KeyConflict`]
+    ErrorOnConflict,
+    /// If both values are maps, they are merged recursively with this same policy.
+    /// Otherwise the value being merged in replaces the existing one.
+    ///
+    /// Schemes that aren't self-describing, like postcard and bincode, can't tell whether a
+    /// loaded value is a map, so nested maps loaded from them are replaced rather than
+    /// merged. Only values that are held as a [`Freeform`](crate::Freeform) are merged.
+    DeepMerge,
+}
+
+pub(crate) type Combiner<S> =
+    Arc<dyn Fn(&mut Sord<S>, Sord<S>) -> Result<(), FreeformErr<S>> + Send + Sync>;
+
+/// A [`MergePolicy`] along with custom combiners for specific keys, used by
+/// [`Freeform::merge`](crate::Freeform::merge)
+///
+/// Combiners take priority over the policy, and only apply to top-level keys.
+#[cfg(feature = "json")]
+#[derive(Clone)]
+pub struct MergeStrategy<S: SerdeScheme = Json> {
+    pub(crate) policy: MergePolicy,
+    pub(crate) combiners: HashMap<String, Combiner<S>>,
+}
+
+#[cfg(not(feature = "json"))]
+#[derive(Clone)]
+pub struct MergeStrategy<S: SerdeScheme> {
+    pub(crate) policy: MergePolicy,
+    pub(crate) combiners: HashMap<String, Combiner<S>>,
+}
+
+impl<S: SerdeScheme> MergeStrategy<S> {
+    pub fn new(policy: MergePolicy) -> Self {
+        MergeStrategy {
+            policy,
+            combiners: HashMap::new(),
+        }
+    }
+
+    pub fn policy(&self) -> MergePolicy {
+        self.policy
+    }
+
+    /// Registers a function that combines the value being merged in into the existing
+    /// value for a key, such as extending a `Vec` or adding numbers together
+    pub fn combine<T: FreeformData, F>(&mut self, key: Key<T>, combiner: F) -> &mut Self
+    where
+        F: Fn(&mut T, T) + Send + Sync + 'static,
+    {
+        self.combiners.insert(
+            key.name().to_string(),
+            Arc::new(move |existing, incoming| {
+                let incoming = incoming.into_de::<T>()?;
+                combiner(existing.de_mut::<T>()?, incoming);
+                Ok(())
+            }),
+        );
+        self
+    }
+}
+
+impl<S: SerdeScheme> Default for MergeStrategy<S> {
+    fn default() -> Self {
+        Self::new(MergePolicy::default())
+    }
+}
+
+impl<S: SerdeScheme> fmt::Debug for MergeStrategy<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MergeStrategy")
+            .field("policy", &self.policy)
+            .field("combiners", &self.combiners.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
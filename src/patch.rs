@@ -0,0 +1,305 @@
+#[cfg(feature = "json-patch")]
+use json_patch::jsonptr::PointerBuf;
+#[cfg(feature = "json-patch")]
+use json_patch::{AddOperation, PatchOperation, RemoveOperation, ReplaceOperation};
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "json-patch")]
+use thiserror::Error;
+
+#[cfg(feature = "json")]
+use crate::Json;
+use crate::SerdeScheme;
+#[cfg(feature = "json-patch")]
+use crate::{Freeform, FreeformErr, Sord};
+
+/// A single key-level change in a [`FreeformPatch`]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(bound = "", rename_all = "snake_case")]
+pub enum PatchOp<S: SerdeScheme> {
+    /// The key is added with this value
+    Add { key: String, value: S::Value },
+    /// The key is removed
+    Remove { key: String },
+    /// The existing value for the key is replaced with this value
+    Change { key: String, value: S::Value },
+}
+
+impl<S: SerdeScheme> PatchOp<S> {
+    pub fn key(&self) -> &str {
+        match self {
+            PatchOp::Add { key, .. } | PatchOp::Remove { key } | PatchOp::Change { key, .. } => key,
+        }
+    }
+}
+
+/// A list of key-level changes between two Freeforms, produced by
+/// [`Freeform::diff`](crate::Freeform::diff) and applied with
+/// [`Freeform::apply_patch`](crate::Freeform::apply_patch)
+///
+/// Patches can be serialized to send just the changes to a Freeform elsewhere.
+#[cfg(feature = "json")]
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(bound = "", transparent)]
+pub struct FreeformPatch<S: SerdeScheme = Json> {
+    ops: Vec<PatchOp<S>>,
+}
+
+#[cfg(not(feature = "json"))]
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(bound = "", transparent)]
+pub struct FreeformPatch<S: SerdeScheme> {
+    ops: Vec<PatchOp<S>>,
+}
+
+impl<S: SerdeScheme> FreeformPatch<S> {
+    pub fn new(ops: Vec<PatchOp<S>>) -> Self {
+        FreeformPatch { ops }
+    }
+
+    pub fn ops(&self) -> &[PatchOp<S>] {
+        &self.ops
+    }
+
+    pub fn into_ops(self) -> Vec<PatchOp<S>> {
+        self.ops
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ops.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.ops.len()
+    }
+}
+
+impl<S: SerdeScheme> FromIterator<PatchOp<S>> for FreeformPatch<S> {
+    fn from_iter<I: IntoIterator<Item = PatchOp<S>>>(iter: I) -> Self {
+        Self::new(iter.into_iter().collect())
+    }
+}
+
+impl<S: SerdeScheme> IntoIterator for FreeformPatch<S> {
+    type Item = PatchOp<S>;
+    type IntoIter = std::vec::IntoIter<PatchOp<S>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.ops.into_iter()
+    }
+}
+
+/// Error from applying a JSON Patch or JSON Merge Patch document to a Freeform
+#[cfg(feature = "json-patch")]
+#[derive(Debug, Error)]
+pub enum JsonPatchError {
+    #[error("could not read freeform as json: {0}")]
+    Freeform(#[from] FreeformErr<Json>),
+    #[error("could not apply json patch: {0}")]
+    Patch(#[from] json_patch::PatchError),
+    #[error("patched document is not an object")]
+    NotAnObject,
+}
+
+#[cfg(feature = "json-patch")]
+impl FreeformPatch<Json> {
+    /// Converts this patch into an RFC 6902 JSON Patch document
+    pub fn to_json_patch(&self) -> json_patch::Patch {
+        let ops = self.ops.iter().map(|op| {
+            let path = PointerBuf::from_tokens([op.key()]);
+            match op {
+                PatchOp::Add { value, .. } => PatchOperation::Add(AddOperation {
+                    path,
+                    value: value.clone(),
+                }),
+                PatchOp::Remove { .. } => PatchOperation::Remove(RemoveOperation { path }),
+                PatchOp::Change { value, .. } => PatchOperation::Replace(ReplaceOperation {
+                    path,
+                    value: value.clone(),
+                }),
+            }
+        });
+        json_patch::Patch(ops.collect())
+    }
+
+    /// Converts this patch into an RFC 7386 JSON Merge Patch document
+    ///
+    /// Merge patches remove keys by setting them to `null`, so a key added or changed to
+    /// `null` is removed instead when the merge patch is applied.
+    pub fn to_merge_patch(&self) -> serde_json::Value {
+        let map = self.ops.iter().map(|op| match op {
+            PatchOp::Add { key, value } | PatchOp::Change { key, value } => {
+                (key.clone(), value.clone())
+            }
+            PatchOp::Remove { key } => (key.clone(), serde_json::Value::Null),
+        });
+        serde_json::Value::Object(map.collect())
+    }
+}
+
+#[cfg(feature = "json-patch")]
+impl Freeform<Json> {
+    /// Applies an RFC 6902 JSON Patch document
+    ///
+    /// Operations can reach into nested values, but only the top-level entries that end up
+    /// different are replaced. If the patch fails, this Freeform is left unchanged.
+    pub fn apply_json_patch(&mut self, patch: &json_patch::Patch) -> Result<(), JsonPatchError> {
+        self.apply_to_document(|doc| json_patch::patch(doc, &patch.0))
+    }
+
+    /// Applies an RFC 7386 JSON Merge Patch document
+    pub fn apply_merge_patch(&mut self, patch: &serde_json::Value) -> Result<(), JsonPatchError> {
+        self.apply_to_document(|doc| {
+            json_patch::merge(doc, patch);
+            Ok(())
+        })
+    }
+
+    fn apply_to_document<F>(&mut self, f: F) -> Result<(), JsonPatchError>
+    where
+        F: FnOnce(&mut serde_json::Value) -> Result<(), json_patch::PatchError>,
+    {
+        let old = self
+            .iter()
+            .map(|(key, sord)| Ok((key.to_string(), sord.value()?.clone())))
+            .collect::<Result<serde_json::Map<_, _>, FreeformErr<Json>>>()?;
+        let mut doc = serde_json::Value::Object(old.clone());
+        f(&mut doc)?;
+        let serde_json::Value::Object(new) = doc else {
+            return Err(JsonPatchError::NotAnObject);
+        };
+
+        self.retain(|key, _| new.contains_key(key));
+        for (key, value) in new {
+            if old.get(&key) != Some(&value) {
+                self.0.insert(key, Sord::from_value(value));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(all(test, feature = "json"))]
+mod test {
+    use typed_key::{typed_key, Key};
+
+    use crate::{Freeform, FreeformErr, FreeformPatch, PatchOp};
+
+    const NAME: Key<String> = typed_key!("name");
+    const TAGS: Key<Vec<String>> = typed_key!("tags");
+    const SIZE: Key<usize> = typed_key!("size");
+
+    fn before_and_after() -> (Freeform, Freeform) {
+        let mut before = Freeform::new();
+        before.put(NAME, "old".to_string()).unwrap();
+        before.put(TAGS, vec!["a".to_string()]).unwrap();
+        before.put(SIZE, 3).unwrap();
+
+        let mut after = before.clone();
+        after.put(NAME, "new".to_string()).unwrap();
+        after.remove(TAGS);
+        after
+            .put(typed_key!("owner"), "someone".to_string())
+            .unwrap();
+        (before, after)
+    }
+
+    #[test]
+    pub fn diff_test() {
+        let (before, after) = before_and_after();
+        let patch = before.diff(&after).unwrap();
+        let keys: Vec<_> = patch.ops().iter().map(PatchOp::key).collect();
+        assert_eq!(vec!["name", "owner", "tags"], keys);
+        assert!(matches!(patch.ops()[0], PatchOp::Change { .. }));
+        assert!(matches!(patch.ops()[1], PatchOp::Add { .. }));
+        assert!(matches!(patch.ops()[2], PatchOp::Remove { .. }));
+        assert!(before.diff(&before).unwrap().is_empty());
+
+        // Patches survive being sent elsewhere
+        let patch: FreeformPatch =
+            serde_json::from_str(&serde_json::to_string(&patch).unwrap()).unwrap();
+        let mut patched = before.clone();
+        patched.apply_patch(&patch).unwrap();
+        assert!(patched.diff(&after).unwrap().is_empty());
+        assert_eq!("new", patched.get_required(NAME).unwrap());
+        assert_eq!(&3, patched.get_required(SIZE).unwrap());
+
+        let mut missing = Freeform::new();
+        assert!(matches!(
+            missing.apply_patch(&patch),
+            Err(FreeformErr::RequiredKeyNotFound(key)) if key == "name"
+        ));
+    }
+
+    #[test]
+    pub fn diff_matches_eq_test() {
+        use std::collections::HashMap;
+
+        // Json can't serialize maps with non-string keys
+        let bad_key: Key<HashMap<(i32, i32), i32>> = typed_key!("bad");
+        let (before, mut after) = before_and_after();
+        after.put(bad_key, HashMap::from([((1, 2), 3)])).unwrap();
+        assert!(matches!(
+            before.diff(&after),
+            Err(FreeformErr::KeySerdeError { key, .. }) if key == "bad"
+        ));
+        assert!(after.diff(&after.clone()).unwrap().is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "cbor")]
+    pub fn diff_ignores_map_order_test() {
+        use std::collections::HashMap;
+
+        // Cbor keeps maps in the order they were built, which varies between HashMaps
+        let map_key: Key<HashMap<String, usize>> = typed_key!("map");
+        let map: HashMap<String, usize> = (0..20).map(|i| (i.to_string(), i)).collect();
+        let mut first = Freeform::<crate::Cbor>::new();
+        first.put(map_key, map.clone()).unwrap();
+        let mut second = Freeform::<crate::Cbor>::new();
+        second.put(map_key, map.into_iter().collect()).unwrap();
+        assert_eq!(first, second);
+        assert!(first.diff(&second).unwrap().is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "json-patch")]
+    pub fn json_patch_test() {
+        use crate::JsonPatchError;
+
+        let (before, after) = before_and_after();
+        let patch = before.diff(&after).unwrap();
+
+        let mut patched = before.clone();
+        patched.apply_json_patch(&patch.to_json_patch()).unwrap();
+        assert!(patched.diff(&after).unwrap().is_empty());
+
+        let mut patched = before.clone();
+        patched.apply_merge_patch(&patch.to_merge_patch()).unwrap();
+        assert!(patched.diff(&after).unwrap().is_empty());
+
+        // Nested changes only touch the entry they reach into
+        let nested: json_patch::Patch = serde_json::from_value(serde_json::json!([
+            { "op": "add", "path": "/tags/-", "value": "b" },
+            { "op": "test", "path": "/size", "value": 3 },
+        ]))
+        .unwrap();
+        let mut patched = before.clone();
+        patched.apply_json_patch(&nested).unwrap();
+        assert_eq!(
+            &vec!["a".to_string(), "b".to_string()],
+            patched.get_required(TAGS).unwrap()
+        );
+
+        let failing: json_patch::Patch = serde_json::from_value(serde_json::json!([
+            { "op": "remove", "path": "/name" },
+            { "op": "test", "path": "/size", "value": 4 },
+        ]))
+        .unwrap();
+        let mut patched = before.clone();
+        assert!(matches!(
+            patched.apply_json_patch(&failing),
+            Err(JsonPatchError::Patch(_))
+        ));
+        assert!(patched.diff(&before).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,346 @@
+use std::any::{type_name, TypeId};
+use std::collections::HashSet;
+
+use thiserror::Error;
+use typed_key::Key;
+
+#[cfg(feature = "json")]
+use crate::Json;
+use crate::{Freeform, FreeformData, SerdeScheme, Sord, SordError};
+
+/// A set of key declarations that a Freeform can be validated against all at once
+///
+/// Each key is registered with its type and whether it is required. The same key name
+/// can be registered with multiple types, in which case it must deserialize as all of them.
+#[cfg(feature = "json")]
+#[derive(Clone, Debug, Default)]
+pub struct KeyRegistry<S: SerdeScheme = Json> {
+    keys: Vec<KeyRegistration<S>>,
+    deny_unknown_keys: bool,
+}
+
+#[cfg(not(feature = "json"))]
+#[derive(Clone, Debug, Default)]
+pub struct KeyRegistry<S: SerdeScheme> {
+    keys: Vec<KeyRegistration<S>>,
+    deny_unknown_keys: bool,
+}
+
+#[derive(Clone, Debug)]
+struct KeyRegistration<S: SerdeScheme> {
+    name: &'static str,
+    type_id: TypeId,
+    type_name: &'static str,
+    required: bool,
+    check: fn(&Sord<S>) -> Result<(), SordError<S>>,
+    #[cfg(feature = "schemars")]
+    schema: Option<fn(&mut schemars::SchemaGenerator) -> schemars::Schema>,
+}
+
+#[derive(Clone, Debug, Error)]
+pub enum ValidationError<S: SerdeScheme> {
+    #[error("required key not found [{0}]")]
+    RequiredKeyNotFound(String),
+    #[error("key [{key}] was stored as a type other than {type_name}")]
+    KeyTypeDoesNotMatch {
+        key: String,
+        type_name: &'static str,
+    },
+    #[error("key [{key}] could not be deserialized as {type_name}: {error}")]
+    SerdeError {
+        key: String,
+        type_name: &'static str,
+        error: S::Error,
+    },
+    #[error("key [{0}] is not registered")]
+    UnknownKey(String),
+}
+
+impl<S: SerdeScheme> KeyRegistry<S> {
+    pub fn new() -> Self {
+        KeyRegistry {
+            keys: Vec::new(),
+            deny_unknown_keys: false,
+        }
+    }
+
+    /// Registers a key that doesn't need to be present
+    pub fn optional<T: FreeformData>(&mut self, key: Key<T>) -> &mut Self {
+        self.register(key, false);
+        self
+    }
+
+    /// Registers a key that must be present
+    pub fn required<T: FreeformData>(&mut self, key: Key<T>) -> &mut Self {
+        self.register(key, true);
+        self
+    }
+
+    /// Registers a key that doesn't need to be present, including its type in the
+    /// generated JSON Schema
+    #[cfg(feature = "schemars")]
+    pub fn optional_with_schema<T: FreeformData + schemars::JsonSchema>(
+        &mut self,
+        key: Key<T>,
+    ) -> &mut Self {
+        self.register(key, false).schema = Some(|generator| generator.subschema_for::<T>());
+        self
+    }
+
+    /// Registers a key that must be present, including its type in the generated JSON Schema
+    #[cfg(feature = "schemars")]
+    pub fn required_with_schema<T: FreeformData + schemars::JsonSchema>(
+        &mut self,
+        key: Key<T>,
+    ) -> &mut Self {
+        self.register(key, true).schema = Some(|generator| generator.subschema_for::<T>());
+        self
+    }
+
+    /// If set, keys that aren't registered are reported as errors during validation
+    pub fn deny_unknown_keys(&mut self, deny: bool) -> &mut Self {
+        self.deny_unknown_keys = deny;
+        self
+    }
+
+    fn register<T: FreeformData>(
+        &mut self,
+        key: Key<T>,
+        required: bool,
+    ) -> &mut KeyRegistration<S> {
+        let type_id = TypeId::of::<T>();
+        let index = if let Some(index) = self.keys.iter().position(|registration| {
+            registration.name == key.name() && registration.type_id == type_id
+        }) {
+            index
+        } else {
+            self.keys.push(KeyRegistration {
+                name: key.name(),
+                type_id,
+                type_name: type_name::<T>(),
+                required: false,
+                check: |sord| sord.de::<T>().map(|_| ()),
+                #[cfg(feature = "schemars")]
+                schema: None,
+            });
+            self.keys.len() - 1
+        };
+        let registration = &mut self.keys[index];
+        registration.required |= required;
+        registration
+    }
+
+    /// Whether a key name has been registered with any type
+    pub fn contains(&self, name: &str) -> bool {
+        self.keys
+            .iter()
+            .any(|registration| registration.name == name)
+    }
+
+    /// Deserializes every registered key present in the Freeform, reporting every missing
+    /// required key, type mismatch and deserialization error found
+    pub fn validate(&self, freeform: &Freeform<S>) -> Result<(), Vec<ValidationError<S>>> {
+        let mut errors = Vec::new();
+        for registration in &self.keys {
+            let Some(sord) = freeform.get_sord(registration.name) else {
+                if registration.required {
+                    errors.push(ValidationError::RequiredKeyNotFound(
+                        registration.name.to_string(),
+                    ));
+                }
+                continue;
+            };
+            match (registration.check)(sord) {
+                Ok(()) => {}
+                Err(SordError::WrongTypeError) => {
+                    errors.push(ValidationError::KeyTypeDoesNotMatch {
+                        key: registration.name.to_string(),
+                        type_name: registration.type_name,
+                    })
+                }
+                Err(SordError::SeDeError(error)) => errors.push(ValidationError::SerdeError {
+                    key: registration.name.to_string(),
+                    type_name: registration.type_name,
+                    error,
+                }),
+            }
+        }
+        if self.deny_unknown_keys {
+            let registered: HashSet<&str> = self
+                .keys
+                .iter()
+                .map(|registration| registration.name)
+                .collect();
+            let mut unknown_keys: Vec<&str> = freeform
+                .keys()
+                .filter(|key| !registered.contains(key))
+                .collect();
+            unknown_keys.sort();
+            errors.extend(
+                unknown_keys
+                    .into_iter()
+                    .map(|key| ValidationError::UnknownKey(key.to_string())),
+            );
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl KeyRegistry<Json> {
+    /// Generates a JSON Schema for a Freeform with the registered keys, as it looks when
+    /// serialized with the `Json` scheme.
+    ///
+    /// Keys registered without a schema accept any value, and a key registered with multiple
+    /// types must match all of their schemas. Unknown keys are only rejected by the schema
+    /// if [`KeyRegistry::deny_unknown_keys`] is set.
+    pub fn json_schema(&self) -> schemars::Schema {
+        use serde_json::{Map, Value};
+
+        let mut generator = schemars::SchemaGenerator::default();
+        let mut property_schemas: Vec<(&str, Vec<Value>)> = Vec::new();
+        let mut required: Vec<&str> = Vec::new();
+        for registration in &self.keys {
+            let schema = match registration.schema {
+                Some(schema_fn) => schema_fn(&mut generator).to_value(),
+                None => Value::Bool(true),
+            };
+            match property_schemas
+                .iter_mut()
+                .find(|(name, _)| *name == registration.name)
+            {
+                Some((_, schemas)) => schemas.push(schema),
+                None => property_schemas.push((registration.name, vec![schema])),
+            }
+            if registration.required && !required.contains(&registration.name) {
+                required.push(registration.name);
+            }
+        }
+
+        let properties: Map<String, Value> = property_schemas
+            .into_iter()
+            .map(|(name, mut schemas)| {
+                let schema = if schemas.len() == 1 {
+                    schemas.remove(0)
+                } else {
+                    serde_json::json!({ "allOf": schemas })
+                };
+                (name.to_string(), schema)
+            })
+            .collect();
+
+        let mut schema = Map::new();
+        if let Some(meta_schema) = generator.settings().meta_schema.as_deref() {
+            schema.insert("$schema".to_string(), meta_schema.into());
+        }
+        schema.insert("type".to_string(), "object".into());
+        schema.insert("properties".to_string(), properties.into());
+        schema.insert("required".to_string(), required.into());
+        if self.deny_unknown_keys {
+            schema.insert("additionalProperties".to_string(), false.into());
+        }
+        let definitions = generator.take_definitions(true);
+        if !definitions.is_empty() {
+            let definitions_path = generator
+                .settings()
+                .definitions_path
+                .trim_start_matches('/');
+            schema.insert(definitions_path.to_string(), definitions.into());
+        }
+        schemars::Schema::from(schema)
+    }
+}
+
+#[cfg(all(test, feature = "json"))]
+mod test {
+    use std::collections::HashMap;
+
+    use typed_key::{typed_key, Key};
+
+    use super::{KeyRegistry, ValidationError};
+    use crate::Freeform;
+
+    const NAME_KEY: Key<String> = typed_key!("name");
+    const COUNT_KEY: Key<u32> = typed_key!("count");
+    const TAGS_KEY: Key<Vec<String>> = typed_key!("tags");
+    const MAP_KEY: Key<HashMap<String, u32>> = typed_key!("map");
+
+    fn registry() -> KeyRegistry {
+        let mut registry = KeyRegistry::new();
+        registry
+            .required(NAME_KEY)
+            .required(COUNT_KEY)
+            .optional(TAGS_KEY)
+            .optional(MAP_KEY);
+        registry
+    }
+
+    #[test]
+    fn validate_valid_freeform() {
+        let freeform =
+            <Freeform>::deserialize("{\"name\":\"thing\",\"count\":3,\"extra\":true}").unwrap();
+        assert!(registry().validate(&freeform).is_ok());
+        assert!(registry()
+            .deny_unknown_keys(true)
+            .validate(&freeform)
+            .is_err());
+    }
+
+    #[test]
+    fn validate_reports_every_error() {
+        let mut freeform = <Freeform>::deserialize("{\"count\":\"three\",\"extra\":true}").unwrap();
+        let wrong_type_key: Key<String> = typed_key!("map");
+        freeform
+            .put(wrong_type_key, "not a map".to_string())
+            .unwrap();
+
+        let errors = registry()
+            .deny_unknown_keys(true)
+            .validate(&freeform)
+            .unwrap_err();
+        assert_eq!(4, errors.len(), "{errors:?}");
+        assert!(matches!(&errors[0], ValidationError::RequiredKeyNotFound(key) if key == "name"));
+        assert!(matches!(&errors[1], ValidationError::SerdeError { key, .. } if key == "count"));
+        assert!(
+            matches!(&errors[2], ValidationError::KeyTypeDoesNotMatch { key, .. } if key == "map")
+        );
+        assert!(matches!(&errors[3], ValidationError::UnknownKey(key) if key == "extra"));
+    }
+
+    #[test]
+    #[cfg(feature = "schemars")]
+    fn json_schema_from_registry() {
+        #[derive(serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
+        struct Point {
+            x: i32,
+            y: i32,
+        }
+
+        let point_key: Key<Point> = typed_key!("point");
+        let mut registry = KeyRegistry::<crate::Json>::new();
+        registry
+            .required_with_schema(NAME_KEY)
+            .optional_with_schema(COUNT_KEY)
+            .optional_with_schema(point_key)
+            .required(TAGS_KEY)
+            .deny_unknown_keys(true);
+
+        let schema = registry.json_schema().to_value();
+        assert_eq!("object", schema["type"]);
+        assert_eq!(serde_json::json!(["name", "tags"]), schema["required"]);
+        assert_eq!(serde_json::json!(false), schema["additionalProperties"]);
+        assert_eq!("string", schema["properties"]["name"]["type"]);
+        assert_eq!("integer", schema["properties"]["count"]["type"]);
+        assert_eq!(serde_json::json!(true), schema["properties"]["tags"]);
+        assert_eq!(
+            serde_json::json!({"$ref": "#/$defs/Point"}),
+            schema["properties"]["point"]
+        );
+        assert_eq!("object", schema["$defs"]["Point"]["type"]);
+    }
+}
@@ -1,41 +1,104 @@
-use std::any::Any;
 use std::borrow::Borrow;
+#[cfg(any(
+    feature = "json",
+    feature = "yaml",
+    feature = "bincode",
+    feature = "cbor",
+    feature = "msgpack"
+))]
 use std::sync::Arc;
 
 use serde::de::DeserializeOwned;
-use serde::Serialize;
-#[cfg(feature = "toml")]
+use serde::{Deserialize, Serialize};
+#[cfg(any(feature = "toml", feature = "cbor", feature = "msgpack"))]
 use thiserror::Error;
 
 /// A trait indicating a scheme for seralizing and deserializing data using Serde
 ///
 /// Can be implmented for other serialization schemes, then create a Freeform<Scheme> to
-/// use that scheme for serializing data
+/// use that scheme for serializing data. Text formats use `str`/`String` for their
+/// serialized data, while binary formats can use `[u8]`/`Vec<u8>`.
 pub trait SerdeScheme: Clone + std::fmt::Debug + Default {
     /// Errors that can be returned from serializing/deserializing.
     type Error: std::fmt::Debug + std::fmt::Display + Clone;
     /// The native "value" representation for the scheme. Used when
     /// serializing/deserializing the freeform as a whole so that
     /// the values aren't stored as strings
-    type Value: DeserializeOwned + Serialize;
+    type Value: DeserializeOwned + Serialize + Clone + std::fmt::Debug;
+    /// The borrowed form of serialized data, such as `str` for text formats or
+    /// `[u8]` for binary formats
+    type Se: ?Sized + std::fmt::Debug + ToOwned<Owned = Self::SeBuf>;
+    /// The owned buffer that serialized data is stored in, such as `String` or `Vec<u8>`
+    type SeBuf: Borrow<Self::Se> + Clone + std::fmt::Debug + Send + Sync + 'static;
+
+    /// Deserialize serialized data into a T
+    fn deserialize<T: DeserializeOwned>(input: &Self::Se) -> Result<T, Self::Error>;
+    /// Serialize a T into a buffer
+    fn serialize<T: Serialize>(input: &T) -> Result<Self::SeBuf, Self::Error>;
+
+    /// Converts a native value into serialized data, used when deserializing a Freeform
+    /// as a whole. The default implementation serializes the value, but schemes whose
+    /// `Value` is an opaque blob of already serialized data can store it directly.
+    fn value_to_se(value: &Self::Value) -> Result<Self::SeBuf, Self::Error> {
+        Self::serialize(value)
+    }
+
+    /// Converts serialized data into a native value, used when serializing a Freeform
+    /// as a whole. Counterpart to [`SerdeScheme::value_to_se`].
+    fn se_to_value(se: &Self::Se) -> Result<Self::Value, Self::Error> {
+        Self::deserialize(se)
+    }
 
-    /// Deserialize a string into a T
-    fn deserialize<T: DeserializeOwned>(input: &str) -> Result<T, Self::Error>;
-    /// Serialize a T into a string
-    fn serialize<T: Serialize>(input: &T) -> Result<String, Self::Error>;
+    /// Deserialize a native value into a T. The default implementation goes through the
+    /// serialized form, schemes that can deserialize their values directly should override it.
+    fn deserialize_value<T: DeserializeOwned>(value: &Self::Value) -> Result<T, Self::Error> {
+        Self::deserialize(Self::value_to_se(value)?.borrow())
+    }
 
-    /// Used for Freeform internals, default implementation should be sufficient
-    ///
-    /// # Safety
-    /// Should only be called in sitatuions where we KNOW de is type T
-    ///
-    unsafe fn serialize_as_any<T: Serialize + 'static + Send + Sync>(
-        input: &Arc<dyn Any + Send + Sync + 'static>,
-    ) -> Result<String, Self::Error> {
-        Self::serialize::<T>(input.clone().downcast::<T>().expect("this method should not be called unless we are sure the downcast will be successful").borrow())
+    /// Serialize a T into a native value. The default implementation goes through the
+    /// serialized form, schemes that can serialize to their values directly should override it.
+    fn serialize_value<T: Serialize>(input: &T) -> Result<Self::Value, Self::Error> {
+        Self::se_to_value(Self::serialize(input)?.borrow())
+    }
+
+    /// Whether a native value is a map, which
+    /// [`MergePolicy::DeepMerge`](crate::MergePolicy::DeepMerge) merges key by key. Schemes
+    /// with opaque values can't tell, so this is false by default.
+    fn value_is_map(_value: &Self::Value) -> bool {
+        false
+    }
+
+    /// Checks that this scheme's values describe their own structure, which converting them
+    /// to another scheme needs. Schemes with opaque values return the error their format
+    /// gives for data it can't read without knowing its type.
+    fn check_self_describing() -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    /// Sorts every map nested in a value, for schemes whose values keep maps in the order
+    /// they were built. Does nothing by default.
+    fn canonicalize_value(_value: &mut Self::Value) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    /// Serialize a T with every map sorted, so that equal data always serializes to identical
+    /// output. The default implementation goes through [`SerdeScheme::canonicalize_value`].
+    fn serialize_canonical<T: Serialize>(input: &T) -> Result<Self::SeBuf, Self::Error> {
+        let mut value = Self::serialize_value(input)?;
+        Self::canonicalize_value(&mut value)?;
+        Self::value_to_se(&value)
     }
 }
 
+/// A scheme that can deserialize data borrowing from the serialized input, such as `&str`,
+/// `Cow<str>` or structs using `#[serde(borrow)]`
+///
+/// Not every serde format supports this, so it is a separate trait from [`SerdeScheme`].
+pub trait BorrowingSerdeScheme: SerdeScheme {
+    /// Deserialize a T that may borrow from the input
+    fn deserialize_borrowed<'a, T: Deserialize<'a>>(input: &'a Self::Se) -> Result<T, Self::Error>;
+}
+
 #[cfg(feature = "json")]
 #[derive(Clone, Debug, Default)]
 pub struct Json;
@@ -45,6 +108,8 @@ impl SerdeScheme for Json {
     // Using an Arc because serde_json doesn't implement Clone
     type Error = Arc<serde_json::Error>;
     type Value = serde_json::Value;
+    type Se = str;
+    type SeBuf = String;
     fn deserialize<'a, T: DeserializeOwned>(input: &str) -> Result<T, Self::Error> {
         serde_json::from_str(input).map_err(Arc::new)
     }
@@ -52,6 +117,30 @@ impl SerdeScheme for Json {
     fn serialize<T: Serialize>(input: &T) -> Result<String, Self::Error> {
         serde_json::to_string(input).map_err(Arc::new)
     }
+
+    fn deserialize_value<T: DeserializeOwned>(value: &Self::Value) -> Result<T, Self::Error> {
+        T::deserialize(value).map_err(Arc::new)
+    }
+
+    fn serialize_value<T: Serialize>(input: &T) -> Result<Self::Value, Self::Error> {
+        serde_json::to_value(input).map_err(Arc::new)
+    }
+
+    fn value_is_map(value: &serde_json::Value) -> bool {
+        value.is_object()
+    }
+
+    /// Writes RFC 8785 canonical JSON
+    fn serialize_canonical<T: Serialize>(input: &T) -> Result<String, Self::Error> {
+        Ok(crate::jcs::to_string(&Self::serialize_value(input)?))
+    }
+}
+
+#[cfg(feature = "json")]
+impl BorrowingSerdeScheme for Json {
+    fn deserialize_borrowed<'a, T: Deserialize<'a>>(input: &'a str) -> Result<T, Self::Error> {
+        serde_json::from_str(input).map_err(Arc::new)
+    }
 }
 
 #[cfg(feature = "ron")]
@@ -62,6 +151,8 @@ pub struct Ron;
 impl SerdeScheme for Ron {
     type Error = ron::Error;
     type Value = ron::Value;
+    type Se = str;
+    type SeBuf = String;
 
     fn deserialize<T: DeserializeOwned>(input: &str) -> Result<T, Self::Error> {
         Ok(ron::de::from_str(input)?)
@@ -70,6 +161,39 @@ impl SerdeScheme for Ron {
     fn serialize<T: Serialize>(input: &T) -> Result<String, Self::Error> {
         ron::to_string(input)
     }
+
+    fn value_is_map(value: &ron::Value) -> bool {
+        matches!(value, ron::Value::Map(_))
+    }
+
+    fn canonicalize_value(value: &mut ron::Value) -> Result<(), Self::Error> {
+        match value {
+            ron::Value::Map(map) => {
+                let mut entries: Vec<_> = std::mem::take(map).into_iter().collect();
+                for (key, val) in entries.iter_mut() {
+                    Self::canonicalize_value(key)?;
+                    Self::canonicalize_value(val)?;
+                }
+                entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+                *map = entries.into_iter().collect();
+            }
+            ron::Value::Seq(items) => {
+                for item in items {
+                    Self::canonicalize_value(item)?;
+                }
+            }
+            ron::Value::Option(Some(inner)) => Self::canonicalize_value(inner)?,
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "ron")]
+impl BorrowingSerdeScheme for Ron {
+    fn deserialize_borrowed<'a, T: Deserialize<'a>>(input: &'a str) -> Result<T, Self::Error> {
+        Ok(ron::de::from_str(input)?)
+    }
 }
 
 #[cfg(feature = "toml")]
@@ -90,10 +214,372 @@ pub enum TomlError {
 impl SerdeScheme for Toml {
     type Error = TomlError;
     type Value = toml::Value;
+    type Se = str;
+    type SeBuf = String;
     fn deserialize<T: DeserializeOwned>(input: &str) -> Result<T, Self::Error> {
         Ok(toml::de::from_str(input)?)
     }
     fn serialize<T: Serialize>(input: &T) -> Result<String, Self::Error> {
         Ok(toml::ser::to_string(input)?)
     }
+    fn deserialize_value<T: DeserializeOwned>(value: &Self::Value) -> Result<T, Self::Error> {
+        Ok(value.clone().try_into()?)
+    }
+    fn serialize_value<T: Serialize>(input: &T) -> Result<Self::Value, Self::Error> {
+        Ok(toml::Value::try_from(input)?)
+    }
+
+    fn value_is_map(value: &toml::Value) -> bool {
+        value.is_table()
+    }
+
+    fn canonicalize_value(value: &mut toml::Value) -> Result<(), Self::Error> {
+        match value {
+            toml::Value::Table(table) => {
+                let mut entries: Vec<_> = std::mem::take(table).into_iter().collect();
+                for (_, val) in entries.iter_mut() {
+                    Self::canonicalize_value(val)?;
+                }
+                entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+                *table = entries.into_iter().collect();
+            }
+            toml::Value::Array(items) => {
+                for item in items {
+                    Self::canonicalize_value(item)?;
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "yaml")]
+#[derive(Clone, Debug, Default)]
+pub struct Yaml;
+
+#[cfg(feature = "yaml")]
+impl SerdeScheme for Yaml {
+    // Using an Arc because serde_yaml_ng doesn't implement Clone
+    type Error = Arc<serde_yaml_ng::Error>;
+    type Value = serde_yaml_ng::Value;
+    type Se = str;
+    type SeBuf = String;
+
+    fn deserialize<T: DeserializeOwned>(input: &str) -> Result<T, Self::Error> {
+        serde_yaml_ng::from_str(input).map_err(Arc::new)
+    }
+
+    fn serialize<T: Serialize>(input: &T) -> Result<String, Self::Error> {
+        serde_yaml_ng::to_string(input).map_err(Arc::new)
+    }
+
+    fn deserialize_value<T: DeserializeOwned>(value: &Self::Value) -> Result<T, Self::Error> {
+        T::deserialize(value).map_err(Arc::new)
+    }
+
+    fn serialize_value<T: Serialize>(input: &T) -> Result<Self::Value, Self::Error> {
+        serde_yaml_ng::to_value(input).map_err(Arc::new)
+    }
+
+    fn value_is_map(value: &serde_yaml_ng::Value) -> bool {
+        value.is_mapping()
+    }
+
+    // Yaml keys can be any value, so they are sorted by their serialized form
+    fn canonicalize_value(value: &mut serde_yaml_ng::Value) -> Result<(), Self::Error> {
+        match value {
+            serde_yaml_ng::Value::Mapping(mapping) => {
+                let mut entries = std::mem::take(mapping)
+                    .into_iter()
+                    .map(|(mut key, mut val)| {
+                        Self::canonicalize_value(&mut key)?;
+                        Self::canonicalize_value(&mut val)?;
+                        Ok((Self::serialize(&key)?, key, val))
+                    })
+                    .collect::<Result<Vec<_>, Self::Error>>()?;
+                entries.sort_by(|(a, ..), (b, ..)| a.cmp(b));
+                *mapping = entries
+                    .into_iter()
+                    .map(|(_, key, val)| (key, val))
+                    .collect();
+            }
+            serde_yaml_ng::Value::Sequence(items) => {
+                for item in items {
+                    Self::canonicalize_value(item)?;
+                }
+            }
+            serde_yaml_ng::Value::Tagged(tagged) => Self::canonicalize_value(&mut tagged.value)?,
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "yaml")]
+impl BorrowingSerdeScheme for Yaml {
+    fn deserialize_borrowed<'a, T: Deserialize<'a>>(input: &'a str) -> Result<T, Self::Error> {
+        serde_yaml_ng::from_str(input).map_err(Arc::new)
+    }
+}
+
+/// The `Value` for schemes that aren't self-describing, such as postcard and bincode.
+///
+/// These formats can't deserialize data without knowing its type ahead of time, so the
+/// values of a Freeform are stored as opaque blobs of already serialized data. A Freeform
+/// using one of these schemes is written as a map of strings to length-prefixed byte
+/// strings, each holding the serialized form of that entry.
+#[cfg(any(feature = "postcard", feature = "bincode"))]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct OpaqueValue(pub Vec<u8>);
+
+#[cfg(any(feature = "postcard", feature = "bincode"))]
+impl Serialize for OpaqueValue {
+    fn serialize<Ser: serde::Serializer>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error> {
+        serializer.serialize_bytes(&self.0)
+    }
+}
+
+#[cfg(any(feature = "postcard", feature = "bincode"))]
+impl<'de> serde::Deserialize<'de> for OpaqueValue {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct OpaqueValueVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for OpaqueValueVisitor {
+            type Value = OpaqueValue;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("a byte string")
+            }
+
+            fn visit_bytes<E: serde::de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+                Ok(OpaqueValue(v.to_vec()))
+            }
+
+            fn visit_byte_buf<E: serde::de::Error>(self, v: Vec<u8>) -> Result<Self::Value, E> {
+                Ok(OpaqueValue(v))
+            }
+
+            fn visit_seq<A: serde::de::SeqAccess<'de>>(
+                self,
+                mut seq: A,
+            ) -> Result<Self::Value, A::Error> {
+                let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or_default());
+                while let Some(byte) = seq.next_element()? {
+                    bytes.push(byte);
+                }
+                Ok(OpaqueValue(bytes))
+            }
+        }
+
+        deserializer.deserialize_byte_buf(OpaqueValueVisitor)
+    }
+}
+
+#[cfg(feature = "postcard")]
+#[derive(Clone, Debug, Default)]
+pub struct Postcard;
+
+#[cfg(feature = "postcard")]
+impl SerdeScheme for Postcard {
+    type Error = postcard::Error;
+    type Value = OpaqueValue;
+    type Se = [u8];
+    type SeBuf = Vec<u8>;
+
+    fn deserialize<T: DeserializeOwned>(input: &[u8]) -> Result<T, Self::Error> {
+        postcard::from_bytes(input)
+    }
+
+    fn serialize<T: Serialize>(input: &T) -> Result<Vec<u8>, Self::Error> {
+        postcard::to_stdvec(input)
+    }
+
+    fn value_to_se(value: &OpaqueValue) -> Result<Vec<u8>, Self::Error> {
+        Ok(value.0.clone())
+    }
+
+    fn se_to_value(se: &[u8]) -> Result<OpaqueValue, Self::Error> {
+        Ok(OpaqueValue(se.to_vec()))
+    }
+
+    fn check_self_describing() -> Result<(), Self::Error> {
+        Err(postcard::Error::WontImplement)
+    }
+}
+
+#[cfg(feature = "postcard")]
+impl BorrowingSerdeScheme for Postcard {
+    fn deserialize_borrowed<'a, T: Deserialize<'a>>(input: &'a [u8]) -> Result<T, Self::Error> {
+        postcard::from_bytes(input)
+    }
+}
+
+#[cfg(feature = "bincode")]
+#[derive(Clone, Debug, Default)]
+pub struct Bincode;
+
+#[cfg(feature = "bincode")]
+impl SerdeScheme for Bincode {
+    // Using an Arc because bincode errors don't implement Clone
+    type Error = Arc<bincode::ErrorKind>;
+    type Value = OpaqueValue;
+    type Se = [u8];
+    type SeBuf = Vec<u8>;
+
+    fn deserialize<T: DeserializeOwned>(input: &[u8]) -> Result<T, Self::Error> {
+        bincode::deserialize(input).map_err(Arc::from)
+    }
+
+    fn serialize<T: Serialize>(input: &T) -> Result<Vec<u8>, Self::Error> {
+        bincode::serialize(input).map_err(Arc::from)
+    }
+
+    fn value_to_se(value: &OpaqueValue) -> Result<Vec<u8>, Self::Error> {
+        Ok(value.0.clone())
+    }
+
+    fn se_to_value(se: &[u8]) -> Result<OpaqueValue, Self::Error> {
+        Ok(OpaqueValue(se.to_vec()))
+    }
+
+    fn check_self_describing() -> Result<(), Self::Error> {
+        Err(Arc::new(bincode::ErrorKind::DeserializeAnyNotSupported))
+    }
+}
+
+#[cfg(feature = "bincode")]
+impl BorrowingSerdeScheme for Bincode {
+    fn deserialize_borrowed<'a, T: Deserialize<'a>>(input: &'a [u8]) -> Result<T, Self::Error> {
+        bincode::deserialize(input).map_err(Arc::from)
+    }
+}
+
+#[cfg(feature = "cbor")]
+#[derive(Clone, Debug, Default)]
+pub struct Cbor;
+
+/// Cbor has different error types for serializing and deserializing, this wraps both of them
+#[cfg(feature = "cbor")]
+#[derive(Clone, Debug, Error)]
+pub enum CborError {
+    #[error(transparent)]
+    De(Arc<ciborium::de::Error<std::io::Error>>),
+    #[error(transparent)]
+    Ser(Arc<ciborium::ser::Error<std::io::Error>>),
+}
+
+#[cfg(feature = "cbor")]
+impl SerdeScheme for Cbor {
+    type Error = CborError;
+    type Value = ciborium::Value;
+    type Se = [u8];
+    type SeBuf = Vec<u8>;
+
+    fn deserialize<T: DeserializeOwned>(input: &[u8]) -> Result<T, Self::Error> {
+        ciborium::from_reader(input).map_err(|err| CborError::De(Arc::new(err)))
+    }
+
+    fn serialize<T: Serialize>(input: &T) -> Result<Vec<u8>, Self::Error> {
+        let mut buffer = Vec::new();
+        ciborium::into_writer(input, &mut buffer).map_err(|err| CborError::Ser(Arc::new(err)))?;
+        Ok(buffer)
+    }
+
+    fn value_is_map(value: &ciborium::Value) -> bool {
+        value.is_map()
+    }
+
+    // Keys are sorted by their encoded bytes, as in RFC 8949's deterministic encoding
+    fn canonicalize_value(value: &mut ciborium::Value) -> Result<(), Self::Error> {
+        match value {
+            ciborium::Value::Map(entries) => {
+                for (key, val) in entries.iter_mut() {
+                    Self::canonicalize_value(key)?;
+                    Self::canonicalize_value(val)?;
+                }
+                let mut keyed = std::mem::take(entries)
+                    .into_iter()
+                    .map(|entry| Ok((Self::serialize(&entry.0)?, entry)))
+                    .collect::<Result<Vec<_>, Self::Error>>()?;
+                keyed.sort_by(|(a, _), (b, _)| a.cmp(b));
+                *entries = keyed.into_iter().map(|(_, entry)| entry).collect();
+            }
+            ciborium::Value::Array(items) => {
+                for item in items {
+                    Self::canonicalize_value(item)?;
+                }
+            }
+            ciborium::Value::Tag(_, inner) => Self::canonicalize_value(inner)?,
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "msgpack")]
+#[derive(Clone, Debug, Default)]
+pub struct MessagePack;
+
+/// MessagePack has different error types for serializing and deserializing, this wraps both of them
+#[cfg(feature = "msgpack")]
+#[derive(Clone, Debug, Error)]
+pub enum MessagePackError {
+    #[error(transparent)]
+    De(Arc<rmp_serde::decode::Error>),
+    #[error(transparent)]
+    Ser(Arc<rmp_serde::encode::Error>),
+}
+
+#[cfg(feature = "msgpack")]
+impl SerdeScheme for MessagePack {
+    type Error = MessagePackError;
+    type Value = rmpv::Value;
+    type Se = [u8];
+    type SeBuf = Vec<u8>;
+
+    fn deserialize<T: DeserializeOwned>(input: &[u8]) -> Result<T, Self::Error> {
+        rmp_serde::from_slice(input).map_err(|err| MessagePackError::De(Arc::new(err)))
+    }
+
+    // Structs are written as maps so that they can be read back as a `rmpv::Value`
+    fn serialize<T: Serialize>(input: &T) -> Result<Vec<u8>, Self::Error> {
+        rmp_serde::to_vec_named(input).map_err(|err| MessagePackError::Ser(Arc::new(err)))
+    }
+
+    fn value_is_map(value: &rmpv::Value) -> bool {
+        value.is_map()
+    }
+
+    // Keys are sorted by their encoded bytes
+    fn canonicalize_value(value: &mut rmpv::Value) -> Result<(), Self::Error> {
+        match value {
+            rmpv::Value::Map(entries) => {
+                for (key, val) in entries.iter_mut() {
+                    Self::canonicalize_value(key)?;
+                    Self::canonicalize_value(val)?;
+                }
+                let mut keyed = std::mem::take(entries)
+                    .into_iter()
+                    .map(|entry| Ok((Self::serialize(&entry.0)?, entry)))
+                    .collect::<Result<Vec<_>, Self::Error>>()?;
+                keyed.sort_by(|(a, _), (b, _)| a.cmp(b));
+                *entries = keyed.into_iter().map(|(_, entry)| entry).collect();
+            }
+            rmpv::Value::Array(items) => {
+                for item in items {
+                    Self::canonicalize_value(item)?;
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "msgpack")]
+impl BorrowingSerdeScheme for MessagePack {
+    fn deserialize_borrowed<'a, T: Deserialize<'a>>(input: &'a [u8]) -> Result<T, Self::Error> {
+        rmp_serde::from_slice(input).map_err(|err| MessagePackError::De(Arc::new(err)))
+    }
 }
@@ -1,146 +1,524 @@
-use super::{SerdeScheme, TypedSord};
+use super::{BorrowingSerdeScheme, SerdeScheme, TypedSord};
 
 use std::any::Any;
 use std::borrow::Borrow;
 use std::fmt::Debug;
+use std::hash::{Hash, Hasher};
 use std::sync::{Arc, OnceLock};
 
 use serde::de::DeserializeOwned;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
 
+/// Serialized OR Deserialized data, which lazily converts between its serialized form, its
+/// deserialized value and the scheme's native value, caching each one.
+///
+/// Failed conversions aren't cached, so they are tried again the next time they are requested.
 #[derive(Clone, Debug)]
-#[allow(clippy::type_complexity)]
 pub struct Sord<S: SerdeScheme> {
-    se: OnceLock<Result<String, SordError<S>>>,
-    de: OnceLock<Result<Arc<dyn Any + 'static + Send + Sync>, SordError<S>>>,
-    se_fn: Option<unsafe fn(&Arc<dyn Any + 'static + Send + Sync>) -> Result<String, S::Error>>,
+    origin: Origin<S>,
+    cache: OnceLock<Box<Cache<S>>>,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+/// The form a Sord was created with, which every other form is generated from
+#[derive(Clone, Debug)]
+enum Origin<S: SerdeScheme> {
+    Se(S::SeBuf),
+    De(Arc<dyn DeData>),
+    Value(S::Value),
+}
+
+/// The forms generated from the origin of a Sord so far. Each one is only ever set through a
+/// shared reference, so they can be filled in without locking.
+#[derive(Clone, Debug)]
+struct Cache<S: SerdeScheme> {
+    se: OnceLock<S::SeBuf>,
+    val: OnceLock<S::Value>,
+    de: DeChain,
+}
+
+impl<S: SerdeScheme> Default for Cache<S> {
+    fn default() -> Self {
+        Cache {
+            se: OnceLock::new(),
+            val: OnceLock::new(),
+            de: OnceLock::new(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Error)]
 pub enum SordError<S: SerdeScheme> {
+    #[error("{0}")]
     SeDeError(S::Error),
+    #[error("the requested type doesn't match the deserialized type")]
     WrongTypeError,
 }
 
+/// Errors from re-encoding a Sord with a different scheme
+#[derive(Clone, Debug, Error)]
+pub enum ConvertError<A: SerdeScheme, B: SerdeScheme> {
+    #[error("error reading from source scheme: {0}")]
+    Source(A::Error),
+    #[error("error writing to target scheme: {0}")]
+    Target(B::Error),
+}
+
+/// Deserialized data with its type erased, which can still be serialized with any scheme
+trait DeData: Any + Send + Sync + erased_serde::Serialize {
+    fn as_any(&self) -> &(dyn Any + Send + Sync);
+    fn as_any_mut(&mut self) -> &mut (dyn Any + Send + Sync);
+    fn into_any(self: Arc<Self>) -> Arc<dyn Any + Send + Sync>;
+}
+
+impl<T: Serialize + 'static + Send + Sync> DeData for T {
+    fn as_any(&self) -> &(dyn Any + Send + Sync) {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut (dyn Any + Send + Sync) {
+        self
+    }
+
+    fn into_any(self: Arc<Self>) -> Arc<dyn Any + Send + Sync> {
+        self
+    }
+}
+
+erased_serde::serialize_trait_object!(DeData);
+
+impl Debug for dyn DeData {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("DeData")
+    }
+}
+
+/// The values a Sord has been deserialized as, one per type requested so far. Types are only ever
+/// appended through a shared reference, so this is a linked list of `OnceLock`s that can
+/// grow without locking.
+type DeChain = OnceLock<Box<DeNode>>;
+
+#[derive(Clone, Debug)]
+struct DeNode {
+    de: Arc<dyn DeData>,
+    next: DeChain,
+}
+
+impl DeNode {
+    fn iter(chain: &DeChain) -> impl Iterator<Item = &DeNode> {
+        std::iter::successors(chain.get().map(Box::as_ref), |node| {
+            node.next.get().map(Box::as_ref)
+        })
+    }
+
+    /// Gets the value for a type, appending one created by `init` if there isn't one yet.
+    /// If `init` fails, nothing is appended.
+    fn get_or_try_init<T: 'static, E, F>(chain: &DeChain, init: F) -> Result<&T, E>
+    where
+        F: FnOnce() -> Result<Arc<dyn DeData>, E>,
+    {
+        let mut slot = chain;
+        let mut init = Some(init);
+        let mut pending = None;
+        loop {
+            if let Some(node) = slot.get() {
+                if let Some(de) = node.de.as_any().downcast_ref::<T>() {
+                    return Ok(de);
+                }
+                slot = &node.next;
+                continue;
+            }
+            // Another thread may append a node first, in which case this one is kept to
+            // try again at the end of the chain
+            let node = match (pending.take(), init.take()) {
+                (Some(node), _) => node,
+                (None, Some(init)) => Box::new(DeNode {
+                    de: init()?,
+                    next: OnceLock::new(),
+                }),
+                (None, None) => continue,
+            };
+            pending = slot.set(node).err();
+        }
+    }
+
+    /// Removes the value for a type from the chain, discarding every other value
+    fn take<T: 'static>(chain: DeChain) -> Option<Arc<dyn DeData>> {
+        let mut current = chain.into_inner();
+        while let Some(node) = current {
+            let DeNode { de, next } = *node;
+            if de.as_any().is::<T>() {
+                return Some(de);
+            }
+            current = next.into_inner();
+        }
+        None
+    }
+}
+
 impl<S: SerdeScheme> Sord<S> {
     pub fn from_de_ref<T: 'static + Send + Sync + Serialize>(de: &T) -> Result<Self, SordError<S>> {
         let se = S::serialize::<T>(de).map_err(SordError::SeDeError)?;
-        Ok(Sord {
-            se: OnceLock::from(Ok(se)),
-            de: OnceLock::new(),
-            se_fn: None,
-        })
+        Ok(Self::from_origin(Origin::Se(se)))
     }
 
     pub fn from_de<T: Serialize + 'static + Send + Sync>(de: T) -> Self {
+        Self::from_origin(Origin::De(Arc::new(de)))
+    }
+
+    pub fn from_se<T: Into<S::SeBuf>>(se: T) -> Self {
+        Self::from_origin(Origin::Se(se.into()))
+    }
+
+    /// Stores the scheme's native value as is, without serializing it
+    pub fn from_value(value: S::Value) -> Self {
+        Self::from_origin(Origin::Value(value))
+    }
+
+    fn from_origin(origin: Origin<S>) -> Self {
         Sord {
-            se: OnceLock::new(),
-            de: OnceLock::from(Ok(Arc::new(de) as Arc<dyn Any + 'static + Send + Sync>)),
-            se_fn: Some(S::serialize_as_any::<T>),
+            origin,
+            cache: OnceLock::new(),
         }
     }
 
-    pub fn from_se<T: ToString>(se: T) -> Self {
-        Sord {
-            se: OnceLock::from(Ok(se.to_string())),
-            de: OnceLock::new(),
-            se_fn: None,
+    fn cache(&self) -> &Cache<S> {
+        self.cache.get_or_init(Box::default)
+    }
+
+    fn cached_se(&self) -> Option<&S::Se> {
+        match &self.origin {
+            Origin::Se(se) => Some(se.borrow()),
+            _ => self.cache.get()?.se.get().map(Borrow::borrow),
         }
     }
 
-    pub fn from_value(value: &S::Value) -> Result<Self, SordError<S>> {
-        let se = S::serialize::<S::Value>(value).map_err(SordError::SeDeError)?;
-        Ok(Sord {
-            se: OnceLock::from(Ok(se.to_string())),
-            de: OnceLock::new(),
-            se_fn: None,
+    fn cached_value(&self) -> Option<&S::Value> {
+        match &self.origin {
+            Origin::Value(val) => Some(val),
+            _ => self.cache.get()?.val.get(),
+        }
+    }
+
+    /// Every value this Sord has been deserialized as, starting with the origin
+    fn de_values(&self) -> impl Iterator<Item = &Arc<dyn DeData>> {
+        let origin = match &self.origin {
+            Origin::De(de) => Some(de),
+            _ => None,
+        };
+        let cached = self.cache.get().map(|cache| &cache.de);
+        origin.into_iter().chain(
+            cached
+                .into_iter()
+                .flat_map(DeNode::iter)
+                .map(|node| &node.de),
+        )
+    }
+
+    fn find_de<T: 'static>(&self) -> Option<&Arc<dyn DeData>> {
+        self.de_values().find(|de| de.as_any().is::<T>())
+    }
+
+    /// The value this Sord has already been deserialized as a T, without deserializing it
+    pub(crate) fn cached_de<T: 'static>(&self) -> Option<&T> {
+        self.find_de::<T>()?.as_any().downcast_ref()
+    }
+
+    /// Deserializes this Sord as a T, caching the result.
+    ///
+    /// A Sord can be deserialized as several different types, each of which is cached
+    /// separately. If the data can't be deserialized as T, the scheme's error is returned
+    /// as [`SordError::SeDeError`], whatever types it was deserialized as before. If the
+    /// Sord was created from a deserialized value of another type,
+    /// [`SordError::WrongTypeError`] is returned instead.
+    pub fn de<T: Serialize + DeserializeOwned + 'static + Send + Sync>(
+        &self,
+    ) -> Result<&T, SordError<S>> {
+        if let Origin::De(de) = &self.origin {
+            if let Some(de) = de.as_any().downcast_ref::<T>() {
+                return Ok(de);
+            }
+        }
+        DeNode::get_or_try_init::<T, _, _>(&self.cache().de, || {
+            let de: T = self.deserialize_as().map_err(|err| match self.origin {
+                // Data put as another type doesn't match, whatever the scheme makes of it
+                Origin::De(_) => SordError::WrongTypeError,
+                _ => err,
+            })?;
+            Ok(Arc::new(de))
         })
     }
 
-    pub fn de<T: DeserializeOwned + 'static + Send + Sync>(&self) -> Result<&T, &SordError<S>> {
-        let se = &self.se;
-        self.de
-            .get_or_init(|| {
-                let se = se
-                    .get()
-                    .expect("should not be possible for both se and de to be uninitialized")
-                    .as_ref()
-                    .expect("should not be possible to initialize se as an error");
-                let deserialize: T = S::deserialize(se).map_err(SordError::SeDeError)?;
-                Ok(Arc::new(deserialize))
-            })
-            .as_ref()
-            .and_then(|de| de.downcast_ref::<T>().ok_or(&SordError::WrongTypeError))
-    }
-
-    pub fn se<T: Serialize + 'static>(&self) -> Result<&str, &SordError<S>> {
-        let de = &self.de;
-        self.se
-            .get_or_init(|| {
-                let de = de
-                    .get()
-                    .expect("should not be possible for both de and se to be uninitialized")
-                    .as_ref()
-                    .expect("should not be possible to initialize de as an error")
-                    .downcast_ref::<T>()
-                    .ok_or(SordError::<S>::WrongTypeError)?;
-                S::serialize(de).map_err(SordError::SeDeError)
-            })
-            .as_ref()
-            .map(|cow| cow.borrow())
-    }
-
-    pub fn value(&self) -> Result<S::Value, SordError<S>> {
-        if let Some(Ok(se)) = self.se.get() {
-            S::deserialize(se.as_str()).map_err(SordError::SeDeError)
-        } else if let Some(Ok(de)) = self.de.get() {
-            let se_str = unsafe {
-                // SAEFTY: de is only initialized without se being initialized with de,
-                // and this function is only populated in that case
-                self.se_fn
-                    .expect("se_fn should be created initialized with de")(de)
-                .map_err(SordError::SeDeError)?
-            };
-            S::deserialize(se_str.as_str()).map_err(SordError::SeDeError)
+    /// Deserializes a new T from whichever form of the data is available
+    fn deserialize_as<T: DeserializeOwned>(&self) -> Result<T, SordError<S>> {
+        let result = if let Some(se) = self.cached_se() {
+            S::deserialize(se)
+        } else if let Some(val) = self.cached_value() {
+            S::deserialize_value(val)
         } else {
-            unreachable!("Se or De should be the initial value")
+            S::deserialize(self.serialized()?)
+        };
+        result.map_err(SordError::SeDeError)
+    }
+
+    /// Mutable access to the deserialized value, deserializing it first if needed.
+    ///
+    /// The serialized data, native value and values deserialized as other types are
+    /// invalidated, and will be generated again from the modified value when next
+    /// requested. If the deserialized value is shared with a clone of this Sord, this
+    /// Sord gets its own copy first.
+    pub fn de_mut<T: Serialize + DeserializeOwned + 'static + Send + Sync>(
+        &mut self,
+    ) -> Result<&mut T, SordError<S>> {
+        self.de::<T>()?;
+        let de = self
+            .find_de::<T>()
+            .cloned()
+            .ok_or(SordError::WrongTypeError)?;
+        // Replacing the origin and dropping the cache releases every other reference this
+        // Sord holds to de
+        self.cache.take();
+        self.origin = Origin::De(de);
+        let Origin::De(de) = &mut self.origin else {
+            return Err(SordError::WrongTypeError);
+        };
+        if Arc::get_mut(de).is_none() {
+            let copy: T = S::serialize(&de.as_ref())
+                .and_then(|se| S::deserialize(se.borrow()))
+                .map_err(SordError::SeDeError)?;
+            *de = Arc::new(copy);
         }
+        Arc::get_mut(de)
+            .and_then(|de| de.as_any_mut().downcast_mut::<T>())
+            .ok_or(SordError::WrongTypeError)
     }
 
-    pub fn typed<T: Clone + Serialize + DeserializeOwned + 'static + Send + Sync>(
+    /// Consumes the Sord for its deserialized value, avoiding a clone when possible.
+    ///
+    /// If the deserialized value is shared with a clone of this Sord, a new copy is
+    /// deserialized from the serialized data instead.
+    pub fn into_de<T: Serialize + DeserializeOwned + 'static + Send + Sync>(
         self,
-    ) -> Option<TypedSord<T, S>> {
-        let Sord { se, de, se_fn: _ } = self;
+    ) -> Result<T, SordError<S>> {
+        self.de::<T>()?;
+        let de = self.take_de::<T>().ok_or(SordError::WrongTypeError)?;
+        Arc::try_unwrap(de).or_else(|de| {
+            S::serialize(de.as_ref())
+                .and_then(|se| S::deserialize(se.borrow()))
+                .map_err(SordError::SeDeError)
+        })
+    }
 
-        let se = if let Some(se) = se.into_inner() {
-            match se {
-                Ok(se) => OnceLock::from(Ok(se)),
-                Err(SordError::WrongTypeError) => return None,
-                Err(SordError::SeDeError(err)) => OnceLock::from(Err(err)),
-            }
-        } else {
-            OnceLock::new()
+    /// Consumes the Sord for its value deserialized as a T, if there is one
+    fn take_de<T: 'static + Send + Sync>(self) -> Option<Arc<T>> {
+        let Sord { origin, cache } = self;
+        let de = match origin {
+            Origin::De(de) if de.as_any().is::<T>() => de,
+            _ => DeNode::take::<T>(cache.into_inner()?.de)?,
         };
+        de.into_any().downcast::<T>().ok()
+    }
 
-        let de = if let Some(de) = de.into_inner() {
-            match de {
-                Ok(de) => {
-                    if let Ok(de) = de.downcast::<T>() {
-                        OnceLock::from(Ok(Arc::<T>::unwrap_or_clone(de)))
-                    } else {
-                        return None;
-                    }
-                }
-                Err(SordError::WrongTypeError) => return None,
-                Err(SordError::SeDeError(err)) => OnceLock::from(Err(err)),
+    /// The serialized data for this Sord, serializing it first if needed.
+    ///
+    /// Returns [`SordError::WrongTypeError`] if the Sord was created from a deserialized value
+    /// of a type other than T. Use [`Sord::serialized`] when the type isn't known.
+    pub fn se<T: Serialize + 'static>(&self) -> Result<&S::Se, SordError<S>> {
+        if let Origin::De(de) = &self.origin {
+            if !de.as_any().is::<T>() {
+                return Err(SordError::WrongTypeError);
             }
+        }
+        self.serialized()
+    }
+
+    /// Deserializes a T that can borrow from the serialized data of this Sord, serializing
+    /// it first if needed. Unlike [`Sord::de`] the result isn't cached, since it borrows
+    /// from the Sord instead of being owned by it.
+    pub fn de_borrowed<'a, T: Deserialize<'a>>(&'a self) -> Result<T, SordError<S>>
+    where
+        S: BorrowingSerdeScheme,
+    {
+        S::deserialize_borrowed(self.serialized()?).map_err(SordError::SeDeError)
+    }
+
+    /// The serialized data for this Sord, serializing it first if needed.
+    ///
+    /// Unlike [`Sord::se`], this doesn't need to know the type of the deserialized value,
+    /// so it works for any entry of a Freeform.
+    pub fn serialized(&self) -> Result<&S::Se, SordError<S>> {
+        if let Some(se) = self.cached_se() {
+            return Ok(se);
+        }
+        let se = match &self.origin {
+            Origin::Se(se) => return Ok(se.borrow()),
+            Origin::De(de) => S::serialize(&de.as_ref()),
+            Origin::Value(val) => S::value_to_se(val),
+        }
+        .map_err(SordError::SeDeError)?;
+        Ok(self.cache().se.get_or_init(|| se).borrow())
+    }
+
+    /// The scheme's native value for this Sord, which is cached after the first call
+    pub fn value(&self) -> Result<&S::Value, SordError<S>> {
+        if let Some(val) = self.cached_value() {
+            return Ok(val);
+        }
+        let val = self.origin.to_value().map_err(SordError::SeDeError)?;
+        Ok(self.cache().val.get_or_init(|| val))
+    }
+
+    /// Consumes the Sord for its native value, without cloning it if it was already cached
+    pub fn into_value(self) -> Result<S::Value, SordError<S>> {
+        let cached = self
+            .cache
+            .into_inner()
+            .and_then(|cache| cache.val.into_inner());
+        match (self.origin, cached) {
+            (Origin::Value(val), _) | (_, Some(val)) => Ok(val),
+            (origin, None) => origin.to_value().map_err(SordError::SeDeError),
+        }
+    }
+
+    /// Re-encodes this Sord with another scheme.
+    ///
+    /// If this Sord was created from a deserialized value, it is serialized directly with
+    /// the new scheme and shared with the new Sord. Otherwise this scheme's `Value` is
+    /// converted to the new scheme, which fails with [`ConvertError::Source`] if this scheme
+    /// isn't self-describing. Types the data has been read as since aren't used, as they may
+    /// not hold all of it.
+    pub fn convert<B: SerdeScheme>(&self) -> Result<Sord<B>, ConvertError<S, B>> {
+        if let Origin::De(de) = &self.origin {
+            let val = B::serialize_value(&de.as_ref()).map_err(ConvertError::Target)?;
+            let sord = Sord::from_origin(Origin::De(de.clone()));
+            let _ = sord.cache().val.set(val);
+            Ok(sord)
         } else {
-            OnceLock::new()
+            S::check_self_describing().map_err(ConvertError::Source)?;
+            let val = match self.cached_value() {
+                Some(val) => B::serialize_value(val),
+                None => B::serialize_value(&self.origin.to_value().map_err(ConvertError::Source)?),
+            }
+            .map_err(ConvertError::Target)?;
+            Ok(Sord::from_value(val))
+        }
+    }
+
+    /// Converts into a [`TypedSord`], keeping the cached serialized data and T if present.
+    ///
+    /// Returns `None` if neither is present and the serialized data can't be generated.
+    pub fn typed<T: Clone + Serialize + DeserializeOwned + 'static + Send + Sync>(
+        self,
+    ) -> Option<TypedSord<T, S>> {
+        self.try_typed().ok()
+    }
+
+    /// Like [`Sord::typed`], but returns the error if the serialized data can't be generated
+    pub(crate) fn try_typed<T: Clone + Serialize + DeserializeOwned + 'static + Send + Sync>(
+        self,
+    ) -> Result<TypedSord<T, S>, SordError<S>> {
+        let se = match self.find_de::<T>() {
+            Some(_) => self.cached_se().map(ToOwned::to_owned),
+            // TypedSord needs the serialized data unless it can take the deserialized T
+            None => Some(self.serialized()?.to_owned()),
         };
-        Some(TypedSord { se, de })
+        let de = self.take_de::<T>().map(Arc::unwrap_or_clone);
+        TypedSord::from_parts(se, de).ok_or(SordError::WrongTypeError)
+    }
+
+    /// Caches serialized data that was already generated elsewhere, such as by a [`TypedSord`]
+    pub(crate) fn with_cached_se(self, se: S::SeBuf) -> Self {
+        if self.cached_se().is_none() {
+            let _ = self.cache().se.set(se);
+        }
+        self
+    }
+
+    /// Caches a value that was already deserialized elsewhere, such as by a [`TypedSord`]
+    pub(crate) fn with_cached_de<T: Serialize + 'static + Send + Sync>(self, de: T) -> Self {
+        if self.find_de::<T>().is_none() {
+            let node = DeNode {
+                de: Arc::new(de),
+                next: OnceLock::new(),
+            };
+            let _ = self.cache().de.set(Box::new(node));
+        }
+        self
     }
 }
 
+impl<S: SerdeScheme> Origin<S> {
+    fn to_value(&self) -> Result<S::Value, S::Error> {
+        match self {
+            Origin::Se(se) => S::se_to_value(se.borrow()),
+            Origin::De(de) => S::serialize_value(&de.as_ref()),
+            Origin::Value(val) => Ok(val.clone()),
+        }
+    }
+}
+
+/// Sords are compared by their scheme values, regardless of which form they were created from
+/// or which types they have been deserialized as. Values that only differ in the order of
+/// their maps are equal, see [`SerdeScheme::canonicalize_value`].
+///
+/// Sords that can't be converted to a value are compared by what they were created from
+/// instead, so that equality stays reflexive without mixing up different data: identical
+/// serialized data is equal, and a deserialized value is only equal to itself and its clones.
+/// [`TypedSord`] follows the same policy.
+impl<S: SerdeScheme> PartialEq for Sord<S>
+where
+    S::Value: PartialEq,
+    S::Se: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        match (self.value(), other.value()) {
+            (Ok(this), Ok(other)) => {
+                this == other
+                    || canonical_value::<S>(this)
+                        .is_some_and(|this| Some(this) == canonical_value::<S>(other))
+            }
+            (Err(_), Err(_)) => match (&self.origin, &other.origin) {
+                (Origin::Se(this), Origin::Se(other)) => this.borrow() == other.borrow(),
+                (Origin::De(this), Origin::De(other)) => Arc::ptr_eq(this, other),
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+}
+
+impl<S: SerdeScheme> Eq for Sord<S>
+where
+    S::Value: Eq,
+    S::Se: Eq,
+{
+}
+
+impl<S: SerdeScheme> Hash for Sord<S>
+where
+    S::Value: Hash,
+    S::Se: Hash,
+{
+    // Hashes the canonical value so that values equal up to map order hash the same
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        match (self.value(), &self.origin) {
+            (Ok(value), _) => canonical_value::<S>(value)
+                .as_ref()
+                .unwrap_or(value)
+                .hash(state),
+            (Err(_), Origin::Se(se)) => se.borrow().hash(state),
+            (Err(_), Origin::De(de)) => Arc::as_ptr(de).cast::<()>().hash(state),
+            (Err(_), Origin::Value(_)) => {}
+        }
+    }
+}
+
+fn canonical_value<S: SerdeScheme>(value: &S::Value) -> Option<S::Value> {
+    let mut value = value.clone();
+    S::canonicalize_value(&mut value).ok()?;
+    Some(value)
+}
+
 #[cfg(all(test, feature = "json"))]
 mod test {
     use serde::Deserialize;
@@ -178,7 +556,7 @@ mod test {
         );
         assert!(matches!(
             sord.de::<String>(),
-            Err(&SordError::WrongTypeError)
+            Err(SordError::WrongTypeError)
         ));
     }
 
@@ -195,9 +573,139 @@ mod test {
             sord.de::<TestySeDe>()
                 .expect("should deserialize succcessfully")
         );
-        assert!(matches!(
-            sord.de::<String>(),
-            Err(&SordError::WrongTypeError)
-        ));
+        assert!(matches!(sord.de::<String>(), Err(SordError::SeDeError(_))));
+
+        // The same data can still be read as any type it deserializes as
+        let value = sord
+            .de::<serde_json::Value>()
+            .expect("should deserialize as a json value");
+        assert_eq!(&serde_json::json!(8), &value["count"]);
+        assert_eq!(
+            &test_obj(),
+            sord.de::<TestySeDe>().expect("should still be cached")
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "json")]
+    fn sord_testing_serialized() {
+        let sord = Sord::<Json>::from_de(test_obj());
+        assert_eq!(SERIALIZED, sord.serialized().expect("should serialize"));
+        let sord = Sord::<Json>::from_value(serde_json::json!([1, 2]));
+        assert_eq!("[1,2]", sord.serialized().expect("should serialize"));
+    }
+
+    #[test]
+    #[cfg(feature = "json")]
+    fn sord_testing_failures_not_cached() {
+        let sord = Sord::<Json>::from_se(SERIALIZED);
+        assert!(matches!(sord.de::<String>(), Err(SordError::SeDeError(_))));
+        assert_eq!(
+            &test_obj(),
+            sord.de::<TestySeDe>()
+                .expect("an earlier failure shouldn't stop this")
+        );
+        assert!(matches!(sord.de::<String>(), Err(SordError::SeDeError(_))));
+
+        // Json can't serialize maps with non-string keys
+        let unserializable = std::collections::HashMap::from([((1, 2), 3)]);
+        let sord = Sord::<Json>::from_de(unserializable.clone());
+        assert!(sord.value().is_err());
+        assert!(sord
+            .se::<std::collections::HashMap<(i32, i32), i32>>()
+            .is_err());
+        let typed = crate::TypedSord::<_, Json>::from_de(unserializable);
+        assert!(typed.se().is_err());
+        assert!(typed.se().is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "json")]
+    fn sord_testing_de_mut_other_types() {
+        let mut sord = Sord::<Json>::from_se(SERIALIZED);
+        sord.de::<serde_json::Value>()
+            .expect("should deserialize as a json value");
+        sord.de_mut::<TestySeDe>()
+            .expect("should deserialize successfully")
+            .count = 9;
+        assert_eq!(
+            &serde_json::json!(9),
+            &sord
+                .de::<serde_json::Value>()
+                .expect("should deserialize again from the modified value")["count"]
+        );
+    }
+
+    #[test]
+    fn sord_testing_de_mut_shared() {
+        let mut sord = Sord::<Json>::from_de(test_obj());
+        let clone = sord.clone();
+        sord.de_mut::<TestySeDe>()
+            .expect("should deserialize successfully")
+            .count = 9;
+        assert_eq!(9, sord.de::<TestySeDe>().unwrap().count);
+        assert_eq!(SERIALIZED, clone.serialized().expect("clone is unchanged"));
+        assert_eq!(test_obj(), clone.into_de::<TestySeDe>().unwrap());
+    }
+
+    #[test]
+    fn sord_testing_size() {
+        // The form a Sord was created with, plus a pointer to the lazily allocated caches
+        assert!(
+            std::mem::size_of::<Sord<Json>>()
+                <= std::mem::size_of::<serde_json::Value>() + 2 * std::mem::size_of::<usize>()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "json")]
+    fn sord_testing_from_value() {
+        let value: serde_json::Value = serde_json::from_str(SERIALIZED).unwrap();
+        let sord = Sord::<Json>::from_value(value.clone());
+        assert_eq!(&value, sord.value().expect("value should exist"));
+        assert_eq!(
+            &test_obj(),
+            sord.de::<TestySeDe>()
+                .expect("should deserialize successfully")
+        );
+        let se = sord
+            .se::<TestySeDe>()
+            .expect("should serialize successfully");
+        assert_eq!(
+            value,
+            serde_json::from_str::<serde_json::Value>(se).unwrap()
+        );
+
+        // Serializing a value doesn't depend on the types it has been read as
+        let sord = Sord::<Json>::from_value(serde_json::json!(8));
+        sord.de::<u64>().expect("should deserialize successfully");
+        assert_eq!("8", sord.se::<String>().expect("should serialize"));
+
+        let sord = Sord::<Json>::from_de(test_obj());
+        assert_eq!(value, sord.into_value().expect("should convert to value"));
+    }
+
+    #[test]
+    fn sord_testing_eq() {
+        let from_se = Sord::<Json>::from_se(SERIALIZED);
+        let from_de = Sord::<Json>::from_de(test_obj());
+        let from_value = Sord::<Json>::from_value(serde_json::from_str(SERIALIZED).unwrap());
+        assert_eq!(from_se, from_de);
+        assert_eq!(from_de, from_value);
+        assert_ne!(from_se, Sord::<Json>::from_de(0));
+        assert_eq!(Sord::<Json>::from_se("not json"), Sord::from_se("not json"));
+        assert_ne!(
+            Sord::<Json>::from_se("not json"),
+            Sord::from_se("[also not json")
+        );
+        assert_ne!(Sord::<Json>::from_se("not json"), Sord::from_se("1"));
+
+        // Json can't serialize maps with non-string keys
+        let unserializable = Sord::<Json>::from_de(std::collections::HashMap::from([((1, 2), 3)]));
+        assert_eq!(unserializable, unserializable.clone());
+        assert_ne!(
+            unserializable,
+            Sord::<Json>::from_de(std::collections::HashMap::from([((1, 2), 3)]))
+        );
     }
 }
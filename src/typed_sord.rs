@@ -1,65 +1,281 @@
-use super::SerdeScheme;
+use super::{SerdeScheme, Sord};
 
 use std::borrow::Borrow;
 use std::fmt::Debug;
 use std::sync::OnceLock;
 
 use serde::de::DeserializeOwned;
-use serde::Serialize;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
-#[derive(Debug)]
+/// Serialized OR Deserialized data of a known type. Whichever form it was created with is
+/// kept, and the other form is generated and cached the first time it is requested.
+#[derive(Clone, Debug)]
 pub struct TypedSord<T, S: SerdeScheme> {
-    pub(crate) se: OnceLock<Result<String, S::Error>>,
-    pub(crate) de: OnceLock<Result<T, S::Error>>,
+    state: TypedState<T, S>,
+}
+
+#[derive(Clone, Debug)]
+enum TypedState<T, S: SerdeScheme> {
+    Se { se: S::SeBuf, de: OnceLock<T> },
+    De { de: T, se: OnceLock<S::SeBuf> },
 }
 
 impl<T: DeserializeOwned + Serialize, S: SerdeScheme> TypedSord<T, S> {
     pub fn from_se<K>(se: K) -> Self
     where
-        K: ToString,
+        K: Into<S::SeBuf>,
     {
-        let se: String = se.to_string();
         TypedSord {
-            se: OnceLock::from(Ok(se)),
-            de: OnceLock::new(),
+            state: TypedState::Se {
+                se: se.into(),
+                de: OnceLock::new(),
+            },
         }
     }
 
     pub fn from_de(de: T) -> Self {
         TypedSord {
+            state: TypedState::De {
+                de,
+                se: OnceLock::new(),
+            },
+        }
+    }
+
+    /// Builds a TypedSord from whichever forms are available, if there are any
+    pub(crate) fn from_parts(se: Option<S::SeBuf>, de: Option<T>) -> Option<Self> {
+        let state = match (se, de) {
+            (se, Some(de)) => TypedState::De {
+                de,
+                se: se.map(OnceLock::from).unwrap_or_default(),
+            },
+            (Some(se), None) => TypedState::Se {
+                se,
+                de: OnceLock::new(),
+            },
+            (None, None) => return None,
+        };
+        Some(TypedSord { state })
+    }
+
+    /// Deserializes the value, caching it if successful
+    pub fn de(&self) -> Result<&T, S::Error> {
+        match &self.state {
+            TypedState::De { de, .. } => Ok(de),
+            TypedState::Se { se, de } => {
+                if let Some(de) = de.get() {
+                    return Ok(de);
+                }
+                let value = S::deserialize(se.borrow())?;
+                Ok(de.get_or_init(|| value))
+            }
+        }
+    }
+
+    /// Serializes the value, caching it if successful
+    pub fn se(&self) -> Result<&S::Se, S::Error> {
+        match &self.state {
+            TypedState::Se { se, .. } => Ok(se.borrow()),
+            TypedState::De { de, se } => {
+                if let Some(se) = se.get() {
+                    return Ok(se.borrow());
+                }
+                let value = S::serialize(de)?;
+                Ok(se.get_or_init(|| value).borrow())
+            }
+        }
+    }
+
+    /// Consumes the TypedSord for its value, deserializing it if needed without cloning
+    pub fn into_inner(self) -> Result<T, S::Error> {
+        match self.state {
+            TypedState::De { de, .. } => Ok(de),
+            TypedState::Se { se, de } => match de.into_inner() {
+                Some(de) => Ok(de),
+                None => S::deserialize(se.borrow()),
+            },
+        }
+    }
+
+    /// Mutable access to the value, deserializing it first if needed.
+    ///
+    /// The serialized data is invalidated, and is serialized again from the modified value
+    /// the next time it is requested.
+    pub fn get_mut(&mut self) -> Result<&mut T, S::Error> {
+        match self.state {
+            TypedState::De {
+                ref mut de,
+                ref mut se,
+            } => {
+                se.take();
+                Ok(de)
+            }
+            TypedState::Se { ref se, ref mut de } => {
+                let de = match de.take() {
+                    Some(de) => de,
+                    None => S::deserialize(se.borrow())?,
+                };
+                self.set(de);
+                self.get_mut()
+            }
+        }
+    }
+
+    /// Replaces the value, discarding the serialized data
+    pub fn set(&mut self, de: T) {
+        self.state = TypedState::De {
+            de,
             se: OnceLock::new(),
-            de: OnceLock::from(Ok(de)),
+        };
+    }
+}
+
+impl<T: Default + DeserializeOwned + Serialize, S: SerdeScheme> Default for TypedSord<T, S> {
+    fn default() -> Self {
+        TypedSord::from_de(T::default())
+    }
+}
+
+/// TypedSords are compared by their deserialized values. Like [`Sord`], ones that can't be
+/// deserialized are compared by their serialized data instead, so that equality stays
+/// reflexive without mixing up different data.
+impl<T: PartialEq + DeserializeOwned + Serialize, S: SerdeScheme> PartialEq for TypedSord<T, S>
+where
+    S::Se: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        match (self.de(), other.de()) {
+            (Ok(this), Ok(other)) => this == other,
+            (Err(_), Err(_)) => match (&self.state, &other.state) {
+                (TypedState::Se { se: this, .. }, TypedState::Se { se: other, .. }) => {
+                    this.borrow() == other.borrow()
+                }
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+}
+
+/// Serialized as the scheme's value, the same way as an entry of a Freeform, so it looks
+/// natural when the containing data is serialized with the same scheme.
+impl<T: DeserializeOwned + Serialize, S: SerdeScheme> Serialize for TypedSord<T, S> {
+    fn serialize<Ser: Serializer>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error> {
+        use serde::ser::Error;
+
+        let value = match &self.state {
+            TypedState::Se { se, .. } => S::se_to_value(se.borrow()),
+            TypedState::De { de, .. } => S::serialize_value(de),
+        }
+        .map_err(Ser::Error::custom)?;
+        value.serialize(serializer)
+    }
+}
+
+/// Deserialized as the scheme's value and kept serialized until the T is first requested
+impl<'de, T: DeserializeOwned + Serialize, S: SerdeScheme> Deserialize<'de> for TypedSord<T, S> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
+
+        let value = S::Value::deserialize(deserializer)?;
+        let se = S::value_to_se(&value).map_err(D::Error::custom)?;
+        Ok(TypedSord::from_se(se))
+    }
+}
+
+impl<T: DeserializeOwned + Serialize + 'static + Send + Sync, S: SerdeScheme> From<TypedSord<T, S>>
+    for Sord<S>
+{
+    fn from(typed: TypedSord<T, S>) -> Self {
+        match typed.state {
+            TypedState::Se { se, de } => match de.into_inner() {
+                Some(de) => Sord::from_se(se).with_cached_de(de),
+                None => Sord::from_se(se),
+            },
+            TypedState::De { de, se } => match se.into_inner() {
+                Some(se) => Sord::from_de(de).with_cached_se(se),
+                None => Sord::from_de(de),
+            },
+        }
+    }
+}
+
+#[cfg(all(test, feature = "json"))]
+mod test {
+    use serde::{Deserialize, Serialize};
+    use typed_key::{typed_key, Key};
+
+    use super::TypedSord;
+    use crate::{Freeform, Json, Sord};
+
+    #[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+    struct Payload {
+        name: String,
+        values: Vec<u32>,
+    }
+
+    #[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+    struct Envelope {
+        id: u32,
+        payload: TypedSord<Payload, Json>,
+    }
+
+    const PAYLOAD_KEY: Key<Payload> = typed_key!("payload");
+
+    fn payload() -> Payload {
+        Payload {
+            name: "big".to_string(),
+            values: vec![1, 2, 3],
         }
     }
 
-    pub fn de(&self) -> Result<&T, &S::Error> {
-        let se = &self.se;
-        self.de
-            .get_or_init(|| {
-                let se = se
-                    .get()
-                    .expect("should not be possible for both se and de to be uninitialized")
-                    .as_ref()
-                    .expect("should not be possible to initialize se as an error");
-                S::deserialize(se)
-            })
-            .as_ref()
-    }
-
-    pub fn se(&self) -> Result<&str, &S::Error> {
-        let de = &self.de;
-        let m = self
-            .se
-            .get_or_init(|| {
-                let de = de
-                    .get()
-                    .expect("should not be possible for both de and se to be uninitialized")
-                    .as_ref()
-                    .expect("should not be possible to initialize de as an error");
-                S::serialize(de)
-            })
-            .as_ref()
-            .map(|cow| cow.borrow());
-        m
+    #[test]
+    fn typed_sord_field_test() {
+        let input = "{\"id\":1,\"payload\":{\"name\":\"big\",\"values\":[1,2,3]}}";
+        let envelope: Envelope = serde_json::from_str(input).unwrap();
+        assert_eq!(
+            "{\"name\":\"big\",\"values\":[1,2,3]}",
+            envelope.payload.se().unwrap()
+        );
+        assert_eq!(input, serde_json::to_string(&envelope).unwrap());
+        assert_eq!(TypedSord::from_de(payload()), envelope.payload.clone());
+        assert_eq!(&payload(), envelope.payload.de().unwrap());
+        assert_ne!(envelope, Envelope::default());
+    }
+
+    #[test]
+    fn typed_sord_mutation_test() {
+        let mut typed = TypedSord::<Payload, Json>::from_se("{\"name\":\"big\",\"values\":[]}");
+        typed.get_mut().unwrap().values.push(4);
+        assert_eq!("{\"name\":\"big\",\"values\":[4]}", typed.se().unwrap());
+        typed.get_mut().unwrap().values.push(5);
+        assert_eq!("{\"name\":\"big\",\"values\":[4,5]}", typed.se().unwrap());
+
+        typed.set(payload());
+        assert_eq!(payload(), typed.clone().into_inner().unwrap());
+        let typed = TypedSord::<Payload, Json>::from_se(typed.se().unwrap());
+        assert_eq!(payload(), typed.into_inner().unwrap());
+        assert!(TypedSord::<Payload, Json>::from_se("[]")
+            .into_inner()
+            .is_err());
+
+        let broken = TypedSord::<Payload, Json>::from_se("[]");
+        assert_eq!(broken, broken.clone());
+        assert_ne!(broken, TypedSord::from_se("[1]"));
+    }
+
+    #[test]
+    fn typed_sord_freeform_test() {
+        let mut freeform = <Freeform>::new();
+        freeform.put(PAYLOAD_KEY, payload()).unwrap();
+        let typed = freeform.get_typed_sord(PAYLOAD_KEY).unwrap().unwrap();
+        assert_eq!(&payload(), typed.de().unwrap());
+
+        let sord: Sord<Json> = TypedSord::<Payload, Json>::from_se(typed.se().unwrap()).into();
+        assert_eq!(freeform.get_sord(PAYLOAD_KEY.name()), Some(&sord));
+        let mut other = <Freeform>::new();
+        other.put_typed_sord(PAYLOAD_KEY, typed).unwrap();
+        assert_eq!(freeform, other);
+        assert_eq!(&payload(), other.get_required(PAYLOAD_KEY).unwrap());
     }
 }